@@ -0,0 +1,145 @@
+//! # Noir Runner Derive
+//!
+//! Derives `ToNoir` and `FromNoir` for Rust structs matching Noir structs field-for-field. Use it
+//! through the `derive` feature of `noir-runner`, which re-exports the macro as
+//! `noir_runner::Noir`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Generics, LitStr, parse_macro_input, parse_quote};
+
+/// Derives `ToNoir` and `FromNoir` for a struct with named fields.
+///
+/// Each field converts to the Noir struct field of the same name, use `#[noir(rename = "..")]`
+/// to map it to a different name. Field types must implement `ToNoir` and `FromNoir`, and each
+/// type parameter is bound by the trait being implemented.
+///
+/// `FromNoir` is implemented for `Vec<T>` and `Option<T>` of any `T: FromNoir`, but `ToNoir` only
+/// through `Serialize`. A `Vec` or `Option` of a struct deriving `Noir` therefore decodes, but
+/// does not convert with `ToNoir`, neither directly nor as a field.
+///
+/// The struct must not implement `Serialize`, as `ToNoir` is already implemented for every
+/// serializable type.
+#[proc_macro_derive(Noir, attributes(noir))]
+pub fn derive_noir(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`Noir` can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Noir` can only be derived for structs",
+            ));
+        }
+    };
+
+    let idents = fields
+        .iter()
+        .map(|field| field.ident.clone())
+        .collect::<Vec<_>>();
+    let names = fields
+        .iter()
+        .map(noir_name)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let ident = &input.ident;
+
+    let to_noir_generics = bound_type_params(&input.generics, quote!(::noir_runner::ToNoir));
+    let (impl_generics, ty_generics, where_clause) = to_noir_generics.split_for_impl();
+
+    let from_noir_generics = bound_type_params(&input.generics, quote!(::noir_runner::FromNoir));
+    let (from_impl_generics, _, from_where_clause) = from_noir_generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::noir_runner::ToNoir for #ident #ty_generics #where_clause {
+            fn to_noir(self) -> ::noir_runner::InputValue {
+                ::noir_runner::ToNoir::try_to_noir(self)
+                    .unwrap_or_else(|err| ::core::panic!("failed to convert into Noir: {err}"))
+            }
+
+            fn try_to_noir(
+                self,
+            ) -> ::core::result::Result<::noir_runner::InputValue, ::noir_runner::Error> {
+                ::core::result::Result::Ok(::noir_runner::struct_value([
+                    #((#names, ::noir_runner::ToNoir::try_to_noir(self.#idents)?)),*
+                ]))
+            }
+        }
+
+        impl #from_impl_generics ::noir_runner::FromNoir for #ident #ty_generics
+            #from_where_clause
+        {
+            fn from_noir(
+                value: ::noir_runner::InputValue,
+            ) -> ::core::result::Result<Self, ::noir_runner::Error> {
+                let mut fields = ::noir_runner::__private::expect_struct(value)?;
+
+                ::core::result::Result::Ok(Self {
+                    #(#idents: ::noir_runner::FromNoir::from_noir(
+                        ::noir_runner::__private::take_field(&mut fields, #names)?
+                    )?),*
+                })
+            }
+        }
+    })
+}
+
+/// Adds a `bound` to the where clause for each type parameter of the struct.
+fn bound_type_params(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+
+    let params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+
+    let where_clause = generics.make_where_clause();
+
+    for param in params {
+        where_clause.predicates.push(parse_quote!(#param: #bound));
+    }
+
+    generics
+}
+
+/// Returns the name of the Noir struct field, honouring `#[noir(rename = "..")]`.
+fn noir_name(field: &syn::Field) -> syn::Result<String> {
+    let mut name = field
+        .ident
+        .as_ref()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("noir"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `noir` attribute, expected `rename`"))
+            }
+        })?;
+    }
+
+    Ok(name)
+}
@@ -0,0 +1,29 @@
+//! Helpers called by the code generated by `#[derive(Noir)]`, not part of the public API.
+
+use std::collections::BTreeMap;
+
+use crate::Error;
+use crate::validation::variant_name;
+
+use noirc_abi::input_parser::InputValue;
+
+/// Unwraps the fields of a struct value.
+pub fn expect_struct(value: InputValue) -> Result<BTreeMap<String, InputValue>, Error> {
+    match value {
+        InputValue::Struct(fields) => Ok(fields),
+        value => Err(Error::ReturnTypeMismatch {
+            expected: "Struct".to_owned(),
+            found: variant_name(&value).to_owned(),
+        }),
+    }
+}
+
+/// Removes the named field from the fields of a struct value.
+pub fn take_field(
+    fields: &mut BTreeMap<String, InputValue>,
+    name: &str,
+) -> Result<InputValue, Error> {
+    fields
+        .remove(name)
+        .ok_or_else(|| Error::InvalidOutput(format!("missing field `{name}`")))
+}
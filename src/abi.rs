@@ -1,42 +1,763 @@
 use serde::Serialize;
-use serde_json::Value;
 use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::Error;
+use crate::de::field_to_i128;
+use crate::option::{PRESENCE_FIELD, VALUE_FIELD};
+use crate::parse::is_below_modulus;
+use crate::ser::to_input_value;
+use crate::validation::variant_name;
+
+use acvm::AcirField;
 
 pub use acvm::FieldElement;
+pub use acvm::acir::native_types::{WitnessMap, WitnessStack};
 pub use noirc_abi::input_parser::InputValue;
+pub use noirc_abi::{Abi, AbiType};
+pub use noirc_artifacts::program::ProgramArtifact;
 
+/// Converts a Rust value into an [`InputValue`].
+///
+/// Struct fields take their serialized names, so `#[serde(rename)]` and `#[serde(rename_all)]`
+/// map Rust field names to the field names of the circuit's structs.
+///
+/// Options follow a presence-flag convention at every nesting level: `Some(value)` converts to a
+/// struct `{ has_value: 1, value }` and `None` to `{ has_value: 0 }`, whose `value` is filled
+/// with zeroes matching the ABI when the inputs are encoded. Circuits model optional data with a
+/// `{ has_value: bool, value: T }` struct to match. `None` and `Some(0)` are therefore distinct,
+/// unlike a bare zero field.
+///
+/// Tuples convert to an [`InputValue::Vec`] of their elements, which is how nargo's ABI encodes
+/// a Noir tuple. Fixed-size arrays convert identically, the ABI type of the parameter decides
+/// which of the two the elements are encoded as, so `(1u64, 2u64)` and `[1u64, 2u64]` both match
+/// a `(Field, Field)` or a `[Field; 2]` parameter. Vectors and slices convert identically as well,
+/// the runner checks their length against the declared array length before encoding them and
+/// returns [`Error::LengthMismatch`] if it differs.
+///
+/// A `char` converts to the field element of its Unicode code point, matching a Noir `u8` for
+/// ASCII characters or a `u32` otherwise. Strings convert to an [`InputValue::String`] matching a
+/// `str<N>` parameter, which the runner checks is exactly `N` bytes long before encoding it.
+///
+/// The unit type `()` and JSON `null` convert to a zero field element. A circuit with no return
+/// value returns `None` from [`NoirRunner::run`](crate::NoirRunner::run), so there is no output to
+/// convert.
+///
+/// A negative integer `-n` converts to the field element `p - n`, as the value has no bit width
+/// until it is matched against the ABI. The runner encodes it in two's complement of the declared
+/// signed integer type, as nargo's ABI encoder does, and decodes negative signed outputs back.
 pub trait ToNoir {
+    /// Converts the value into an [`InputValue`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the value cannot be converted, use [`ToNoir::try_to_noir`] to handle the error.
     fn to_noir(self) -> InputValue;
+
+    /// Attempts to convert the value into an [`InputValue`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::NonFiniteFloat`] if the value contains a NaN or infinite float, or
+    /// [`Error::InvalidValue`] if the value contains a float that is not an exact integer or fails
+    /// to serialize.
+    fn try_to_noir(self) -> Result<InputValue, Error>;
 }
 
 impl<T: Serialize> ToNoir for T {
     fn to_noir(self) -> InputValue {
-        match serde_json::to_value(self).unwrap() {
-            Value::Null => InputValue::Field(0u32.into()),
-            Value::Bool(b) => InputValue::Field(b.into()),
-            Value::Number(n) => {
-                if n.is_i64() {
-                    InputValue::Field((n.as_i64().unwrap() as i128).into())
-                } else if n.is_u64() {
-                    InputValue::Field(n.as_u64().unwrap().into())
-                } else {
-                    InputValue::Field((n.as_f64().unwrap() as u64).into())
+        self.try_to_noir()
+            .unwrap_or_else(|err| panic!("failed to convert into Noir: {err}"))
+    }
+
+    fn try_to_noir(self) -> Result<InputValue, Error> {
+        to_input_value(&self)
+    }
+}
+
+/// Compares an [`InputValue`] against a Rust value.
+///
+/// `InputValue` and the primitive types are both defined outside this crate, so `PartialEq`
+/// cannot be implemented between them. Instead, the Rust value is converted with [`ToNoir`] and
+/// the results are compared, so `output.equals(5u64)` holds wherever
+/// `output == 5u64.to_noir()` does.
+///
+/// Absent options are the exception. A decoded `None` holds the zero-filled `value` of its ABI
+/// type while a converted `None` holds none, so options whose `has_value` is `0` are compared by
+/// their flag alone.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{InputValue, NoirEq};
+///
+/// let output = InputValue::Field(5u32.into());
+///
+/// assert!(output.equals(5u64));
+/// assert!(!output.equals("five"));
+/// ```
+pub trait NoirEq {
+    /// Returns `true` if the value equals the converted Rust value, or `false` if they differ or
+    /// the Rust value cannot be converted.
+    fn equals<T: Serialize>(&self, other: T) -> bool;
+}
+
+impl NoirEq for InputValue {
+    fn equals<T: Serialize>(&self, other: T) -> bool {
+        other.try_to_noir().is_ok_and(|other| noir_eq(self, &other))
+    }
+}
+
+fn noir_eq(a: &InputValue, b: &InputValue) -> bool {
+    match (a, b) {
+        (InputValue::Vec(a), InputValue::Vec(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| noir_eq(a, b))
+        }
+        (InputValue::Struct(a), InputValue::Struct(b))
+            if is_absent_option(a) || is_absent_option(b) =>
+        {
+            a.get(PRESENCE_FIELD) == b.get(PRESENCE_FIELD)
+        }
+        (InputValue::Struct(a), InputValue::Struct(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(name, a)| b.get(name).is_some_and(|b| noir_eq(a, b)))
+        }
+        (a, b) => a == b,
+    }
+}
+
+fn is_absent_option(fields: &BTreeMap<String, InputValue>) -> bool {
+    fields.get(PRESENCE_FIELD) == Some(&InputValue::Field(FieldElement::zero()))
+        && fields
+            .keys()
+            .all(|name| name == PRESENCE_FIELD || name == VALUE_FIELD)
+}
+
+/// Converts a decoded [`InputValue`] into a Rust value.
+///
+/// Options follow the same presence-flag convention as [`ToNoir`], a `{ has_value, value }`
+/// struct decodes to `None` if `has_value` is `0` and to `Some` of the decoded `value` if it is
+/// `1`, so optional values compose at any nesting depth. Signed integers are read back as
+/// [`ToNoir`] writes them, negative values wrap around the field modulus. Integers that do not fit
+/// in the requested type return [`Error::ValueOutOfRange`]. The unit type `()` decodes from Noir's
+/// unit, the empty tuple.
+///
+/// Structs implementing `Deserialize` can be decoded with [`from_input_value`], which follows the
+/// same conventions.
+///
+/// [`from_input_value`]: crate::from_input_value
+pub trait FromNoir: Sized {
+    fn from_noir(value: InputValue) -> Result<Self, Error>;
+}
+
+impl FromNoir for FieldElement {
+    fn from_noir(value: InputValue) -> Result<Self, Error> {
+        expect_field(value)
+    }
+}
+
+impl<T: FromNoir> FromNoir for Vec<T> {
+    fn from_noir(value: InputValue) -> Result<Self, Error> {
+        match value {
+            InputValue::Vec(values) => values.into_iter().map(T::from_noir).collect(),
+            value => Err(Error::ReturnTypeMismatch {
+                expected: "Vec".to_owned(),
+                found: variant_name(&value).to_owned(),
+            }),
+        }
+    }
+}
+
+macro_rules! impl_from_noir_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl FromNoir for $ty {
+                fn from_noir(value: InputValue) -> Result<Self, Error> {
+                    let field = expect_field(value)?;
+
+                    if field.num_bits() > <$ty>::BITS {
+                        return Err(out_of_range(field, stringify!($ty)));
+                    }
+
+                    Ok(field.to_u128() as $ty)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_noir_unsigned!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_from_noir_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl FromNoir for $ty {
+                fn from_noir(value: InputValue) -> Result<Self, Error> {
+                    let field = expect_field(value)?;
+
+                    field_to_i128(field)
+                        .and_then(|value| <$ty>::try_from(value).ok())
+                        .ok_or_else(|| out_of_range(field, stringify!($ty)))
                 }
             }
-            Value::Array(a) => InputValue::Vec(a.into_iter().map(|v| v.to_noir()).collect()),
-            Value::String(s) => InputValue::String(s),
-            Value::Object(o) => {
-                let map = o
-                    .into_iter()
-                    .map(|(k, v)| (k, v.to_noir()))
-                    .collect::<BTreeMap<String, InputValue>>();
-
-                InputValue::Struct(map)
+        )*
+    };
+}
+
+impl_from_noir_signed!(i8, i16, i32, i64, i128);
+
+macro_rules! impl_from_noir_tuple {
+    ($($len:literal => ($($ty:ident $value:ident),+)),*) => {
+        $(
+            impl<$($ty: FromNoir),+> FromNoir for ($($ty,)+) {
+                fn from_noir(value: InputValue) -> Result<Self, Error> {
+                    let values = match value {
+                        InputValue::Vec(values) => values,
+                        value => {
+                            return Err(Error::ReturnTypeMismatch {
+                                expected: "Vec".to_owned(),
+                                found: variant_name(&value).to_owned(),
+                            });
+                        }
+                    };
+
+                    let [$($value),+]: [InputValue; $len] =
+                        values.try_into().map_err(|values: Vec<InputValue>| {
+                            Error::InvalidOutput(format!(
+                                "expected a tuple of {} elements, found {}",
+                                $len,
+                                values.len()
+                            ))
+                        })?;
+
+                    Ok(($($ty::from_noir($value)?,)+))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_noir_tuple!(
+    1 => (A a),
+    2 => (A a, B b),
+    3 => (A a, B b, C c),
+    4 => (A a, B b, C c, D d),
+    5 => (A a, B b, C c, D d, E e),
+    6 => (A a, B b, C c, D d, E e, F f)
+);
+
+impl FromNoir for String {
+    fn from_noir(value: InputValue) -> Result<Self, Error> {
+        match value {
+            InputValue::String(string) => Ok(string),
+            value => Err(Error::ReturnTypeMismatch {
+                expected: "String".to_owned(),
+                found: variant_name(&value).to_owned(),
+            }),
+        }
+    }
+}
+
+impl<T: FromNoir> FromNoir for Option<T> {
+    fn from_noir(value: InputValue) -> Result<Self, Error> {
+        let mut fields = match value {
+            InputValue::Struct(fields) => fields,
+            value => {
+                return Err(Error::ReturnTypeMismatch {
+                    expected: "Struct".to_owned(),
+                    found: variant_name(&value).to_owned(),
+                });
             }
+        };
+
+        let (Some(has_value), Some(value)) =
+            (fields.remove(PRESENCE_FIELD), fields.remove(VALUE_FIELD))
+        else {
+            return Err(Error::InvalidOutput(format!(
+                "expected a struct with `{PRESENCE_FIELD}` and `{VALUE_FIELD}` fields"
+            )));
+        };
+
+        if bool::from_noir(has_value)? {
+            T::from_noir(value).map(Some)
+        } else {
+            Ok(None)
         }
     }
 }
 
+impl FromNoir for () {
+    fn from_noir(value: InputValue) -> Result<Self, Error> {
+        match value {
+            InputValue::Vec(values) if values.is_empty() => Ok(()),
+            value => Err(Error::ReturnTypeMismatch {
+                expected: "empty Vec".to_owned(),
+                found: variant_name(&value).to_owned(),
+            }),
+        }
+    }
+}
+
+impl FromNoir for char {
+    fn from_noir(value: InputValue) -> Result<Self, Error> {
+        let field = expect_field(value)?;
+
+        (field.num_bits() <= 32)
+            .then(|| char::from_u32(field.to_u128() as u32))
+            .flatten()
+            .ok_or_else(|| Error::InvalidOutput(format!("expected a char, found {field}")))
+    }
+}
+
+impl FromNoir for bool {
+    fn from_noir(value: InputValue) -> Result<Self, Error> {
+        let field = expect_field(value)?;
+
+        match field {
+            field if field.is_zero() => Ok(false),
+            field if field.is_one() => Ok(true),
+            field => Err(Error::InvalidOutput(format!(
+                "expected a boolean, found {field}"
+            ))),
+        }
+    }
+}
+
+/// Unwraps a field element, returning [`Error::ReturnTypeMismatch`] for any other variant.
+fn expect_field(value: InputValue) -> Result<FieldElement, Error> {
+    match value {
+        InputValue::Field(field) => Ok(field),
+        value => Err(Error::ReturnTypeMismatch {
+            expected: "Field".to_owned(),
+            found: variant_name(&value).to_owned(),
+        }),
+    }
+}
+
+/// Parses a field element from a hexadecimal string, with or without a `0x` prefix.
+///
+/// ## Errors
+///
+/// Returns [`Error::InvalidField`] if the string is not hexadecimal or its value is not below the
+/// field modulus, rather than reducing it.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{FieldElement, field_from_hex};
+///
+/// assert_eq!(field_from_hex("0xff").unwrap(), FieldElement::from(255u32));
+/// assert!(field_from_hex("0xzz").is_err());
+/// ```
+pub fn field_from_hex(hex: &str) -> Result<FieldElement, Error> {
+    let digits = hex.strip_prefix("0x").unwrap_or(hex);
+
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(Error::InvalidField(format!(
+            "`{hex}` is not a hexadecimal number"
+        )));
+    }
+
+    if !is_below_modulus(digits, 16) {
+        return Err(Error::InvalidField(format!(
+            "`{hex}` is not below the field modulus"
+        )));
+    }
+
+    FieldElement::from_hex(&format!("0x{digits}"))
+        .ok_or_else(|| Error::InvalidField(format!("`{hex}` is not a hexadecimal number")))
+}
+
+/// Parses a field element from a decimal string.
+///
+/// Unlike integer conversions with [`ToNoir`], this accepts values beyond `i128`, up to the field
+/// modulus. Negative values are not accepted, negate the parsed field element instead.
+///
+/// ## Errors
+///
+/// Returns [`Error::InvalidField`] if the string is not a decimal number or its value is not below
+/// the field modulus, rather than reducing it.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{FieldElement, field_from_dec_str};
+///
+/// assert_eq!(field_from_dec_str("255").unwrap(), FieldElement::from(255u32));
+/// assert!(field_from_dec_str("-1").is_err());
+/// ```
+pub fn field_from_dec_str(dec: &str) -> Result<FieldElement, Error> {
+    if dec.is_empty() || !dec.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(Error::InvalidField(format!(
+            "`{dec}` is not a decimal number"
+        )));
+    }
+
+    if !is_below_modulus(dec, 10) {
+        return Err(Error::InvalidField(format!(
+            "`{dec}` is not below the field modulus"
+        )));
+    }
+
+    FieldElement::try_from_str(dec)
+        .ok_or_else(|| Error::InvalidField(format!("`{dec}` is not a decimal number")))
+}
+
+fn out_of_range(field: FieldElement, target: &str) -> Error {
+    Error::ValueOutOfRange {
+        value: field.to_string(),
+        target: target.to_owned(),
+    }
+}
+
+/// Converts a field element into a `u64`.
+///
+/// This is [`FromNoir`] for a `u64` applied to a field element, so both fail the same way.
+///
+/// ## Errors
+///
+/// Returns [`Error::ValueOutOfRange`] if the value does not fit in a `u64`, rather than truncating
+/// it.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{FieldElement, field_to_u64};
+///
+/// assert_eq!(field_to_u64(FieldElement::from(42u32)).unwrap(), 42);
+/// assert!(field_to_u64(FieldElement::from(u128::MAX)).is_err());
+/// ```
+pub fn field_to_u64(field: FieldElement) -> Result<u64, Error> {
+    u64::from_noir(InputValue::Field(field))
+}
+
+/// Converts a field element into a `u128`.
+///
+/// ## Errors
+///
+/// Returns [`Error::ValueOutOfRange`] if the value does not fit in a `u128`, rather than
+/// truncating it.
+pub fn field_to_u128(field: FieldElement) -> Result<u128, Error> {
+    u128::from_noir(InputValue::Field(field))
+}
+
+/// Converts a field element into an `i64`.
+///
+/// Negative values are read as their field negation, as Noir encodes a negative `i64` input, so
+/// `-FieldElement::from(1u32)` converts to `-1`.
+///
+/// ## Errors
+///
+/// Returns [`Error::ValueOutOfRange`] if the value does not fit in an `i64`.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{FieldElement, field_to_i64};
+///
+/// assert_eq!(field_to_i64(-FieldElement::from(5u32)).unwrap(), -5);
+/// assert!(field_to_i64(FieldElement::from(u64::MAX as u128)).is_err());
+/// ```
+pub fn field_to_i64(field: FieldElement) -> Result<i64, Error> {
+    i64::from_noir(InputValue::Field(field))
+}
+
+/// The number of bits packed into each field element by [`bits_to_fields`].
+///
+/// The BN254 modulus is 254 bits wide, but not every 254 bit value is below it, so 253 bits is the
+/// largest budget that can never overflow a field element.
+pub const BITS_PER_FIELD: usize = 253;
+
+/// Packs bits into field elements.
+///
+/// Bits are packed little-endian, `bits[0]` is the least significant bit of the first field
+/// element. Each field element holds up to [`BITS_PER_FIELD`] bits, the remaining bits spill into
+/// the next field element.
+///
+/// ## Returns
+///
+/// Returns an [`InputValue::Field`] if the bits fit in a single field element, otherwise an
+/// [`InputValue::Vec`] of fields.
+pub fn bits_to_fields(bits: &[bool]) -> InputValue {
+    let mut fields = bits
+        .chunks(BITS_PER_FIELD)
+        .map(|chunk| {
+            let mut bytes = [0u8; 32];
+
+            for (index, bit) in chunk.iter().enumerate() {
+                bytes[31 - index / 8] |= u8::from(*bit) << (index % 8);
+            }
+
+            InputValue::Field(FieldElement::from_be_bytes_reduce(&bytes))
+        })
+        .collect::<Vec<_>>();
+
+    match fields.len() {
+        0 => InputValue::Field(FieldElement::zero()),
+        1 => fields.remove(0),
+        _ => InputValue::Vec(fields),
+    }
+}
+
+/// Packs bytes into a single field element, reading them as a big-endian integer.
+///
+/// This is the byte order Noir uses natively: `Field::from_be_bytes` and `Field::to_be_bytes`
+/// agree with it, as do the digests of the standard library's hash functions when packed into a
+/// field, so a hash computed in Rust matches the same hash reconstructed in the circuit. The value
+/// is reduced modulo the field if it exceeds the BN254 modulus, which a 32 byte input may.
+///
+/// Pass the bytes as they are, `[u8; N]` inputs are converted with [`ToNoir`] to an array of one
+/// field per byte in the same order, no reversal is needed for either.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{InputValue, bytes_to_field_be};
+///
+/// assert_eq!(bytes_to_field_be(&[1, 0]), InputValue::Field(256u32.into()));
+/// ```
+pub fn bytes_to_field_be(bytes: &[u8]) -> InputValue {
+    InputValue::Field(FieldElement::from_be_bytes_reduce(bytes))
+}
+
+/// Packs bytes into a single field element, reading them as a little-endian integer.
+///
+/// This matches a circuit reconstructing the field with `Field::from_le_bytes`, refer to
+/// [`bytes_to_field_be`] for the byte order Noir uses natively.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{InputValue, bytes_to_field_le};
+///
+/// assert_eq!(bytes_to_field_le(&[1, 0]), InputValue::Field(1u32.into()));
+/// ```
+pub fn bytes_to_field_le(bytes: &[u8]) -> InputValue {
+    InputValue::Field(FieldElement::from_le_bytes_reduce(bytes))
+}
+
+/// Converts a Merkle proof path into an array of field elements.
+///
+/// Each sibling hash is read as a 32 byte big-endian integer and packed into a single field
+/// element, reduced modulo the field if it exceeds the BN254 modulus. The result matches a circuit
+/// parameter of type `[Field; N]`, where `N` is the length of the path.
+pub fn merkle_path_to_fields(path: &[[u8; 32]]) -> InputValue {
+    InputValue::Vec(path.iter().map(|hash| bytes_to_field_be(hash)).collect())
+}
+
+/// Flattens a value into its field element leaves, each paired with its path.
+///
+/// Struct fields are joined with `.` and array elements are indexed with `[i]`, so a struct
+/// `{ a, b: { c: [x, y] } }` flattens to `a`, `b.c[0]` and `b.c[1]`. Strings are flattened to one
+/// field element per byte, as Noir encodes them. A bare field element has an empty path.
+pub fn flatten_result(value: &InputValue) -> Vec<(String, FieldElement)> {
+    fn flatten(path: String, value: &InputValue, leaves: &mut Vec<(String, FieldElement)>) {
+        match value {
+            InputValue::Field(field) => leaves.push((path, *field)),
+            InputValue::String(string) => {
+                for (index, byte) in string.bytes().enumerate() {
+                    leaves.push((
+                        format!("{path}[{index}]"),
+                        FieldElement::from(u128::from(byte)),
+                    ));
+                }
+            }
+            InputValue::Vec(values) => {
+                for (index, value) in values.iter().enumerate() {
+                    flatten(format!("{path}[{index}]"), value, leaves);
+                }
+            }
+            InputValue::Struct(fields) => {
+                for (name, value) in fields {
+                    let path = if path.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{path}.{name}")
+                    };
+
+                    flatten(path, value, leaves);
+                }
+            }
+        }
+    }
+
+    let mut leaves = Vec::new();
+
+    flatten(String::new(), value, &mut leaves);
+
+    leaves
+}
+
+/// Renders a value as JSON, the inverse of converting JSON with [`ToNoir`].
+///
+/// Field elements are rendered as decimal strings, as most exceed the integers JSON numbers can
+/// hold without losing precision. Vectors become arrays, structs become objects and strings stay
+/// strings. Negative integers are rendered as their field element `p - n`, decode them with
+/// [`FromNoir`] to recover the sign.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{ToNoir, input_value_to_json};
+///
+/// let value = serde_json::json!({ "a": 1, "b": [2, 3] }).to_noir();
+///
+/// assert_eq!(
+///     input_value_to_json(&value),
+///     serde_json::json!({ "a": "1", "b": ["2", "3"] })
+/// );
+/// ```
+pub fn input_value_to_json(value: &InputValue) -> serde_json::Value {
+    match value {
+        InputValue::Field(field) => serde_json::Value::String(field_to_decimal(*field)),
+        InputValue::String(string) => serde_json::Value::String(string.clone()),
+        InputValue::Vec(values) => values.iter().map(input_value_to_json).collect(),
+        InputValue::Struct(fields) => fields
+            .iter()
+            .map(|(name, value)| (name.clone(), input_value_to_json(value)))
+            .collect(),
+    }
+}
+
+/// Renders a field element as a decimal integer.
+fn field_to_decimal(field: FieldElement) -> String {
+    let mut bytes = field.to_be_bytes();
+    let mut digits = Vec::new();
+
+    // long division of the big-endian bytes by 10, collecting the remainders
+    while bytes.iter().any(|byte| *byte != 0) {
+        let mut remainder = 0u16;
+
+        for byte in &mut bytes {
+            let value = (remainder << 8) | u16::from(*byte);
+
+            *byte = (value / 10) as u8;
+            remainder = value % 10;
+        }
+
+        digits.push(b'0' + remainder as u8);
+    }
+
+    if digits.is_empty() {
+        return "0".to_owned();
+    }
+
+    digits
+        .iter()
+        .rev()
+        .map(|digit| char::from(*digit))
+        .collect()
+}
+
+/// Converts a duration into a field element of whole seconds, truncating any fractional part.
+///
+/// The seconds of a [`Duration`] fit in a `u64`, well below the field modulus, so the conversion
+/// cannot overflow.
+pub fn duration_as_secs_field(duration: Duration) -> InputValue {
+    InputValue::Field(duration.as_secs().into())
+}
+
+/// Converts a duration into a field element of whole milliseconds, truncating any fractional
+/// part.
+///
+/// The milliseconds of a [`Duration`] fit in a `u128`, below the 254 bit field modulus, so the
+/// conversion cannot overflow.
+pub fn duration_as_millis_field(duration: Duration) -> InputValue {
+    InputValue::Field(duration.as_millis().into())
+}
+
+/// Serializes an input map into a canonical byte representation.
+///
+/// The bytes depend only on the contents of the map, not on how it was constructed, so they are
+/// suitable for hashing, caching and content-addressing inputs. The format is stable across runs
+/// and versions of this crate:
+///
+/// - The map is encoded as a struct, without its tag.
+/// - Lengths are 4 byte big-endian unsigned integers.
+/// - A field element is the tag `0` followed by its 32 byte big-endian representation.
+/// - A string is the tag `1` followed by its length in bytes and its UTF-8 bytes.
+/// - An array is the tag `2` followed by its length and each element in order.
+/// - A struct is the tag `3` followed by its number of fields and each field sorted by name, as
+///   the length of its name in bytes, its UTF-8 name and its value.
+pub fn canonical_input_bytes(input_map: &BTreeMap<String, InputValue>) -> Vec<u8> {
+    fn encode_len(len: usize, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+
+    fn encode_fields(fields: &BTreeMap<String, InputValue>, bytes: &mut Vec<u8>) {
+        encode_len(fields.len(), bytes);
+
+        for (name, value) in fields {
+            encode_len(name.len(), bytes);
+            bytes.extend_from_slice(name.as_bytes());
+            encode(value, bytes);
+        }
+    }
+
+    fn encode(value: &InputValue, bytes: &mut Vec<u8>) {
+        match value {
+            InputValue::Field(field) => {
+                bytes.push(0);
+                bytes.extend_from_slice(&field.to_be_bytes());
+            }
+            InputValue::String(string) => {
+                bytes.push(1);
+                encode_len(string.len(), bytes);
+                bytes.extend_from_slice(string.as_bytes());
+            }
+            InputValue::Vec(values) => {
+                bytes.push(2);
+                encode_len(values.len(), bytes);
+                values.iter().for_each(|value| encode(value, bytes));
+            }
+            InputValue::Struct(fields) => {
+                bytes.push(3);
+                encode_fields(fields, bytes);
+            }
+        }
+    }
+
+    let mut bytes = Vec::new();
+
+    encode_fields(input_map, &mut bytes);
+
+    bytes
+}
+
+/// Constructs an [`InputValue::Struct`] from field name and value pairs.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{ToNoir, struct_value};
+///
+/// let value = struct_value([("a", 1u32.to_noir()), ("b", "x".to_noir())]);
+/// ```
+pub fn struct_value<K: Into<String>>(
+    fields: impl IntoIterator<Item = (K, InputValue)>,
+) -> InputValue {
+    InputValue::Struct(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+}
+
+/// Constructs an [`InputValue::Struct`] from `name: value` pairs.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{ToNoir, noir_struct};
+///
+/// let value = noir_struct! { a: 1u32.to_noir(), b: "x".to_noir() };
+/// ```
+#[macro_export]
+macro_rules! noir_struct {
+    ($($name:ident : $value:expr),* $(,)?) => {
+        $crate::InputValue::Struct(::std::collections::BTreeMap::from([
+            $((::std::string::String::from(stringify!($name)), $value)),*
+        ]))
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,6 +798,17 @@ mod tests {
         assert_eq!(input_value_c, InputValue::Field(1u32.into()));
     }
 
+    #[test]
+    fn test_128_bit_number() {
+        assert_eq!(u128::MAX.to_noir(), InputValue::Field(u128::MAX.into()));
+        assert_eq!(
+            i128::MIN.to_noir(),
+            InputValue::Field(-FieldElement::from(i128::MIN.unsigned_abs()))
+        );
+        assert_eq!(u128::from_noir(u128::MAX.to_noir()).unwrap(), u128::MAX);
+        assert_eq!(i128::from_noir(i128::MIN.to_noir()).unwrap(), i128::MIN);
+    }
+
     #[test]
     fn test_array() {
         let a = vec![1u64, 1u64, 1u64];
@@ -125,6 +857,42 @@ mod tests {
         assert_eq!(input_value, InputValue::Struct(map));
     }
 
+    #[test]
+    fn test_serde_rename() {
+        #[derive(Serialize)]
+        struct Test {
+            #[serde(rename = "outputHash")]
+            output_hash: u32,
+        }
+
+        let input_value = ToNoir::to_noir(Test { output_hash: 1 });
+
+        assert_eq!(input_value, struct_value([("outputHash", 1u32.to_noir())]));
+    }
+
+    #[test]
+    fn test_serde_rename_all() {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Test {
+            first_value: u32,
+            second_value: u32,
+        }
+
+        let input_value = ToNoir::to_noir(Test {
+            first_value: 1,
+            second_value: 2,
+        });
+
+        assert_eq!(
+            input_value,
+            struct_value([
+                ("firstValue", 1u32.to_noir()),
+                ("secondValue", 2u32.to_noir())
+            ])
+        );
+    }
+
     #[test]
     fn test_bytes() {
         let a = [1u8, 2u8, 3u8];
@@ -140,4 +908,498 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn test_struct_value() {
+        let input_value = struct_value([("a", 1u32.to_noir()), ("b", "hello".to_noir())]);
+
+        let map = BTreeMap::from([
+            ("a".to_string(), InputValue::Field(1u32.into())),
+            ("b".to_string(), InputValue::String("hello".to_string())),
+        ]);
+
+        assert_eq!(input_value, InputValue::Struct(map));
+    }
+
+    #[test]
+    fn test_noir_struct() {
+        let input_value = crate::noir_struct! {
+            a: 1u32.to_noir(),
+            b: crate::noir_struct! { c: "hello".to_noir() },
+        };
+
+        let expected = struct_value([
+            ("a", InputValue::Field(1u32.into())),
+            (
+                "b",
+                struct_value([("c", InputValue::String("hello".to_string()))]),
+            ),
+        ]);
+
+        assert_eq!(input_value, expected);
+    }
+
+    #[test]
+    fn test_from_noir_bool() {
+        let a = bool::from_noir(InputValue::Field(0u32.into())).unwrap();
+        let b = bool::from_noir(InputValue::Field(1u32.into())).unwrap();
+        let c = bool::from_noir(InputValue::Field(2u32.into()));
+
+        assert!(!a);
+        assert!(b);
+        assert!(matches!(c, Err(Error::InvalidOutput(_))));
+    }
+
+    #[test]
+    fn test_option() {
+        assert_eq!(
+            Some(1u32).to_noir(),
+            struct_value([("has_value", 1u32.to_noir()), ("value", 1u32.to_noir())])
+        );
+        assert_eq!(
+            None::<u32>.to_noir(),
+            struct_value([("has_value", 0u32.to_noir())])
+        );
+        assert_ne!(None::<u32>.to_noir(), Some(0u32).to_noir());
+    }
+
+    #[test]
+    fn test_tuple() {
+        let value = (1u64, "a".to_owned(), true).to_noir();
+
+        assert_eq!(
+            value,
+            InputValue::Vec(vec![1u64.to_noir(), "a".to_noir(), true.to_noir()])
+        );
+        assert_eq!(
+            <(u64, String, bool)>::from_noir(value).unwrap(),
+            (1, "a".to_owned(), true)
+        );
+        assert_eq!((1u64, 2u64).to_noir(), [1u64, 2u64].to_noir());
+
+        let result = <(u64, u64)>::from_noir(InputValue::Vec(vec![1u64.to_noir()]));
+
+        assert!(matches!(result, Err(Error::InvalidOutput(_))));
+    }
+
+    #[test]
+    fn test_bytes_to_field() {
+        let mut bytes = [0u8; 32];
+        bytes[31] = 1;
+
+        assert_eq!(bytes_to_field_be(&bytes), InputValue::Field(1u32.into()));
+        assert_eq!(
+            bytes_to_field_le(&bytes),
+            InputValue::Field(FieldElement::from(2u128).pow(&FieldElement::from(248u128)))
+        );
+
+        bytes.reverse();
+
+        assert_eq!(bytes_to_field_le(&bytes), InputValue::Field(1u32.into()));
+    }
+
+    #[test]
+    fn test_input_value_to_json() {
+        let value = struct_value([
+            ("a", 0u32.to_noir()),
+            ("b", u128::MAX.to_noir()),
+            ("c", "text".to_noir()),
+            ("d", InputValue::Field(-FieldElement::one())),
+        ]);
+
+        let modulus = FieldElement::modulus();
+        let max = (&modulus - 1u32).to_string();
+
+        assert_eq!(
+            input_value_to_json(&value),
+            serde_json::json!({
+                "a": "0",
+                "b": u128::MAX.to_string(),
+                "c": "text",
+                "d": max,
+            })
+        );
+    }
+
+    #[test]
+    fn test_char() {
+        assert_eq!('a'.to_noir(), InputValue::Field(97u32.into()));
+        assert_eq!(char::from_noir('é'.to_noir()).unwrap(), 'é');
+
+        let result = char::from_noir(InputValue::Field(0xD800u32.into()));
+
+        assert!(matches!(result, Err(Error::InvalidOutput(_))));
+    }
+
+    #[test]
+    fn test_unit() {
+        assert_eq!(().to_noir(), InputValue::Field(0u32.into()));
+
+        <()>::from_noir(InputValue::Vec(Vec::new())).unwrap();
+
+        let result = <()>::from_noir(InputValue::Field(0u32.into()));
+
+        assert!(matches!(result, Err(Error::ReturnTypeMismatch { .. })));
+    }
+
+    #[test]
+    fn test_from_noir_nested_option() {
+        let value = struct_value([
+            ("has_value", 1u32.to_noir()),
+            (
+                "value",
+                struct_value([("has_value", 1u32.to_noir()), ("value", 7u32.to_noir())]),
+            ),
+        ]);
+
+        assert_eq!(
+            Option::<Option<u32>>::from_noir(value).unwrap(),
+            Some(Some(7))
+        );
+
+        let value = struct_value([("has_value", 0u32.to_noir()), ("value", 0u32.to_noir())]);
+
+        assert_eq!(Option::<u32>::from_noir(value).unwrap(), None);
+    }
+
+    #[test]
+    fn test_noir_eq() {
+        assert!(5u64.to_noir().equals(5u64));
+        assert!(5u64.to_noir().equals(5u8));
+        assert!("hello".to_noir().equals("hello"));
+        assert!(vec![1u32, 2].to_noir().equals([1u64, 2]));
+
+        assert!(!5u64.to_noir().equals(6u64));
+        assert!(!"hello".to_noir().equals("world"));
+        assert!(!5u64.to_noir().equals("5"));
+        assert!(!0u64.to_noir().equals(f64::NAN));
+    }
+
+    #[test]
+    fn test_noir_eq_decoded_option() {
+        let none = struct_value([("has_value", 0u32.to_noir()), ("value", 0u32.to_noir())]);
+
+        assert!(none.equals(None::<u32>));
+        assert!(!none.equals(Some(0u32)));
+
+        let some = struct_value([("has_value", 1u32.to_noir()), ("value", 7u32.to_noir())]);
+
+        assert!(some.equals(Some(7u32)));
+        assert!(!some.equals(None::<u32>));
+
+        let nested = struct_value([("has_value", 1u32.to_noir()), ("value", none)]);
+
+        assert!(nested.equals(Some(None::<u32>)));
+    }
+
+    #[test]
+    fn test_from_noir_vec() {
+        let value = vec![1u32, 2, 3].to_noir();
+
+        assert_eq!(Vec::<u32>::from_noir(value).unwrap(), vec![1, 2, 3]);
+        assert!(matches!(
+            Vec::<u32>::from_noir(1u32.to_noir()),
+            Err(Error::ReturnTypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_noir_signed() {
+        assert_eq!(i64::from_noir((-5i64).to_noir()).unwrap(), -5);
+        assert_eq!(i8::from_noir(127i8.to_noir()).unwrap(), 127);
+        assert_eq!(i8::from_noir(i8::MIN.to_noir()).unwrap(), i8::MIN);
+        assert!(matches!(
+            i8::from_noir(128u32.to_noir()),
+            Err(Error::ValueOutOfRange { target, .. }) if target == "i8"
+        ));
+        assert!(matches!(
+            i8::from_noir((-129i64).to_noir()),
+            Err(Error::ValueOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_noir_string() {
+        assert_eq!(String::from_noir("hello".to_noir()).unwrap(), "hello");
+        assert!(matches!(
+            String::from_noir(1u32.to_noir()),
+            Err(Error::ReturnTypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_noir_unsigned_out_of_range() {
+        assert_eq!(u8::from_noir(255u32.to_noir()).unwrap(), 255);
+        assert!(matches!(
+            u8::from_noir(256u32.to_noir()),
+            Err(Error::ValueOutOfRange { target, .. }) if target == "u8"
+        ));
+    }
+
+    #[test]
+    fn test_duration_as_secs_field() {
+        assert_eq!(duration_as_secs_field(Duration::ZERO), 0u32.to_noir());
+        assert_eq!(
+            duration_as_secs_field(Duration::from_millis(1_500)),
+            1u32.to_noir()
+        );
+        assert_eq!(
+            duration_as_secs_field(Duration::from_secs(86_400)),
+            86_400u32.to_noir()
+        );
+        assert_eq!(duration_as_secs_field(Duration::MAX), u64::MAX.to_noir());
+    }
+
+    #[test]
+    fn test_duration_as_millis_field() {
+        assert_eq!(duration_as_millis_field(Duration::ZERO), 0u32.to_noir());
+        assert_eq!(
+            duration_as_millis_field(Duration::from_micros(1_999)),
+            1u32.to_noir()
+        );
+        assert_eq!(
+            duration_as_millis_field(Duration::from_secs(60)),
+            60_000u32.to_noir()
+        );
+        assert_eq!(
+            duration_as_millis_field(Duration::MAX),
+            InputValue::Field(Duration::MAX.as_millis().into())
+        );
+    }
+
+    #[test]
+    fn test_bits_to_fields_8_bits() {
+        let bits = [true, false, true, false, false, false, false, true];
+
+        let input_value = bits_to_fields(&bits);
+
+        assert_eq!(input_value, InputValue::Field(0b1000_0101u32.into()));
+    }
+
+    #[test]
+    fn test_bits_to_fields_254_bits() {
+        let mut bits = vec![false; 254];
+        bits[0] = true;
+        bits[253] = true;
+
+        let input_value = bits_to_fields(&bits);
+
+        assert_eq!(
+            input_value,
+            InputValue::Vec(vec![
+                InputValue::Field(1u32.into()),
+                InputValue::Field(1u32.into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_bits_to_fields_300_bits() {
+        let bits = vec![true; 300];
+
+        let input_value = bits_to_fields(&bits);
+
+        let first =
+            FieldElement::from(2u128).pow(&FieldElement::from(253u128)) - FieldElement::one();
+        let second = FieldElement::from((1u128 << 47) - 1);
+
+        assert_eq!(
+            input_value,
+            InputValue::Vec(vec![InputValue::Field(first), InputValue::Field(second)])
+        );
+    }
+
+    #[test]
+    fn test_merkle_path_to_fields() {
+        let mut hash = [0u8; 32];
+        hash[30] = 1;
+        hash[31] = 2;
+
+        let input_value = merkle_path_to_fields(&[[0u8; 32], hash]);
+
+        assert_eq!(
+            input_value,
+            InputValue::Vec(vec![
+                InputValue::Field(0u32.into()),
+                InputValue::Field(0x0102u32.into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_flatten_result() {
+        let value = struct_value([
+            ("a", InputValue::Field(1u32.into())),
+            (
+                "b",
+                struct_value([(
+                    "c",
+                    InputValue::Vec(vec![
+                        InputValue::Field(2u32.into()),
+                        InputValue::Field(3u32.into()),
+                    ]),
+                )]),
+            ),
+        ]);
+
+        let leaves = flatten_result(&value);
+
+        assert_eq!(
+            leaves,
+            vec![
+                ("a".to_string(), FieldElement::from(1u32)),
+                ("b.c[0]".to_string(), FieldElement::from(2u32)),
+                ("b.c[1]".to_string(), FieldElement::from(3u32)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_canonical_input_bytes() {
+        let a = BTreeMap::from([
+            ("x".to_owned(), 1u32.to_noir()),
+            (
+                "y".to_owned(),
+                struct_value([("b", "hi".to_noir()), ("a", vec![2u32].to_noir())]),
+            ),
+        ]);
+
+        let mut b = BTreeMap::new();
+        b.insert(
+            "y".to_owned(),
+            crate::noir_struct! { a: vec![2u32].to_noir(), b: "hi".to_noir() },
+        );
+        b.insert("x".to_owned(), InputValue::Field(FieldElement::one()));
+
+        assert_eq!(canonical_input_bytes(&a), canonical_input_bytes(&b));
+
+        b.insert("x".to_owned(), 2u32.to_noir());
+
+        assert_ne!(canonical_input_bytes(&a), canonical_input_bytes(&b));
+    }
+
+    #[test]
+    fn test_try_to_noir_serialize_error() {
+        struct Failing;
+
+        impl Serialize for Failing {
+            fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("failing"))
+            }
+        }
+
+        let map = BTreeMap::from([(1u32, 2u32)]);
+
+        assert!(matches!(Failing.try_to_noir(), Err(Error::InvalidValue(_))));
+        assert!(matches!(map.try_to_noir(), Err(Error::InvalidValue(_))));
+    }
+
+    #[test]
+    fn test_non_finite_float() {
+        assert!(matches!(
+            f64::NAN.try_to_noir(),
+            Err(Error::NonFiniteFloat(_))
+        ));
+        assert!(matches!(
+            f64::INFINITY.try_to_noir(),
+            Err(Error::NonFiniteFloat(_))
+        ));
+        assert!(matches!(
+            f64::NEG_INFINITY.try_to_noir(),
+            Err(Error::NonFiniteFloat(_))
+        ));
+    }
+
+    #[test]
+    fn test_fractional_float() {
+        assert_eq!(2.0f64.to_noir(), 2u32.to_noir());
+        assert_eq!((-1.0f64).to_noir(), (-1i64).to_noir());
+        assert!(matches!(2.9f64.try_to_noir(), Err(Error::InvalidValue(_))));
+    }
+
+    #[test]
+    fn test_from_noir_return_type_mismatch() {
+        let value = struct_value([("a", InputValue::Field(1u32.into()))]);
+
+        let result = bool::from_noir(value);
+
+        assert!(matches!(
+            result,
+            Err(Error::ReturnTypeMismatch { expected, found })
+                if expected == "Field" && found == "Struct"
+        ));
+    }
+
+    #[test]
+    fn test_field_from_hex() {
+        assert_eq!(field_from_hex("0x0").unwrap(), FieldElement::zero());
+        assert_eq!(field_from_hex("FF").unwrap(), FieldElement::from(255u32));
+
+        let max = -FieldElement::one();
+
+        assert_eq!(field_from_hex(&max.to_hex()).unwrap(), max);
+
+        let modulus = FieldElement::modulus().to_str_radix(16);
+
+        assert!(matches!(
+            field_from_hex(&modulus),
+            Err(Error::InvalidField(_))
+        ));
+        assert!(matches!(field_from_hex("0x"), Err(Error::InvalidField(_))));
+        assert!(matches!(
+            field_from_hex("0xg1"),
+            Err(Error::InvalidField(_))
+        ));
+    }
+
+    #[test]
+    fn test_field_from_dec_str() {
+        let large = "340282366920938463463374607431768211456";
+
+        assert_eq!(
+            field_from_dec_str(large).unwrap(),
+            FieldElement::from(u128::MAX) + 1u32.into()
+        );
+
+        let modulus = FieldElement::modulus().to_string();
+
+        assert!(matches!(
+            field_from_dec_str(&modulus),
+            Err(Error::InvalidField(_))
+        ));
+        assert!(matches!(
+            field_from_dec_str(""),
+            Err(Error::InvalidField(_))
+        ));
+        assert!(matches!(
+            field_from_dec_str("1.5"),
+            Err(Error::InvalidField(_))
+        ));
+    }
+
+    #[test]
+    fn test_field_to_integers() {
+        assert_eq!(
+            field_to_u64(FieldElement::from(u64::MAX as u128)).unwrap(),
+            u64::MAX
+        );
+        assert_eq!(
+            field_to_u128(FieldElement::from(u128::MAX)).unwrap(),
+            u128::MAX
+        );
+        assert_eq!(field_to_i64(-FieldElement::from(1u32)).unwrap(), -1);
+        assert_eq!(
+            field_to_i64(FieldElement::from(i64::MAX as u128)).unwrap(),
+            i64::MAX
+        );
+
+        assert!(matches!(
+            field_to_u64(FieldElement::from(1u128 << 64)),
+            Err(Error::ValueOutOfRange { target, .. }) if target == "u64"
+        ));
+        assert!(field_to_u128(-FieldElement::from(1u32)).is_err());
+        assert!(field_to_i64(FieldElement::from(1u128 << 63)).is_err());
+        assert!(field_to_i64(-FieldElement::from(1u128 << 63)).is_ok());
+        assert!(field_to_i64(-FieldElement::from((1u128 << 63) + 1)).is_err());
+    }
 }
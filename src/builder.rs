@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::cache::{LruCache, ResultCache};
+use crate::encoder::{InputEncoders, InputTransforms};
+use crate::runner::Artifacts;
+use crate::{Error, NoirRunner, NumberStrategy};
+
+use nargo_toml::{PackageSelection, get_package_manifest, resolve_workspace_from_toml};
+use noirc_abi::Abi;
+use noirc_abi::input_parser::InputValue;
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+
+/// NoirRunner Builder
+///
+/// Configures and constructs a [`NoirRunner`]. Use [`NoirRunner::builder`] to create one.
+#[derive(Debug, Clone)]
+pub struct NoirRunnerBuilder {
+    program_dir: PathBuf,
+    export_directory: Option<PathBuf>,
+    max_cached_artifacts: Option<usize>,
+    cache_artifacts: bool,
+    encoders: InputEncoders,
+    memoized_results: Option<usize>,
+    catch_panics: bool,
+    reduced_fields: bool,
+    latest_version: bool,
+    transforms: InputTransforms,
+    numbers: NumberStrategy,
+    max_brillig_steps: Option<usize>,
+    report_diagnostics: bool,
+    prover_binary: PathBuf,
+}
+
+impl NoirRunnerBuilder {
+    pub(crate) fn new(program_dir: PathBuf) -> Self {
+        Self {
+            program_dir,
+            export_directory: None,
+            max_cached_artifacts: None,
+            cache_artifacts: true,
+            encoders: InputEncoders::default(),
+            memoized_results: None,
+            catch_panics: false,
+            reduced_fields: false,
+            latest_version: false,
+            transforms: InputTransforms::default(),
+            numbers: NumberStrategy::default(),
+            max_brillig_steps: None,
+            report_diagnostics: true,
+            prover_binary: PathBuf::from("bb"),
+        }
+    }
+
+    /// Sets the directory artifacts are read from, instead of deriving it from the `Nargo.toml`
+    /// manifest.
+    ///
+    /// The manifest is not read at all, so the program directory does not need one. It is still
+    /// used by foreign calls that resolve relative paths.
+    pub fn export_directory(mut self, export_directory: PathBuf) -> Self {
+        self.export_directory = Some(export_directory);
+        self
+    }
+
+    /// Sets the maximum number of deserialized programs held in the artifact cache.
+    ///
+    /// Once the limit is exceeded, the least recently used program is evicted and will be read
+    /// from its artifact again on its next run. A limit of `0` bypasses the cache, reading every
+    /// program from its artifact on each run. Defaults to unbounded.
+    pub fn max_cached_artifacts(mut self, max_cached_artifacts: usize) -> Self {
+        self.max_cached_artifacts = Some(max_cached_artifacts);
+        self
+    }
+
+    /// Controls whether deserialized programs are held in the artifact cache.
+    ///
+    /// Disabling the cache reads every program from its artifact on each run, as with a
+    /// [`NoirRunnerBuilder::max_cached_artifacts`] limit of `0`, so regenerated exports are always
+    /// picked up. Enabled by default.
+    pub fn cache(mut self, cache_artifacts: bool) -> Self {
+        self.cache_artifacts = cache_artifacts;
+        self
+    }
+
+    /// Memoizes run results by function and inputs, holding up to `capacity` results.
+    ///
+    /// A run whose program and encoded inputs match a memoized run returns the memoized result
+    /// without executing the program. Only successful runs are memoized and the least recently
+    /// used result is evicted once the capacity is exceeded. Runs that collect resource
+    /// statistics or have a deadline are always executed. Disabled by default.
+    ///
+    /// This is only safe for circuits without side-effecting foreign calls, as memoized runs do
+    /// not make their foreign calls again.
+    pub fn memoize_results(mut self, capacity: usize) -> Self {
+        self.memoized_results = Some(capacity);
+        self
+    }
+
+    /// Converts panics raised while executing a program into [`Error::Panic`].
+    ///
+    /// Execution is run inside [`std::panic::catch_unwind`], so pathological inputs that trigger
+    /// a panic deep in the solver return an error instead of unwinding through the caller. This
+    /// has caveats:
+    ///
+    /// - Panics are only caught if the binary unwinds, with `panic = "abort"` the process still
+    ///   aborts.
+    /// - The panic hook still runs, so the panic message is printed to stderr unless a custom
+    ///   hook is installed.
+    /// - Solver state is discarded after a panic, but foreign call side effects may have happened.
+    ///
+    /// Disabled by default.
+    pub fn catch_panics(mut self, catch_panics: bool) -> Self {
+        self.catch_panics = catch_panics;
+        self
+    }
+
+    /// Rejects textual field inputs that are not reduced modulo the BN254 prime.
+    ///
+    /// Field elements parsed from text by [`NoirRunner::run_from_kv`] are otherwise silently
+    /// reduced, so a value at or above the modulus runs as a different value than intended. With
+    /// this enabled, such values return [`Error::InvalidInput`] instead. Field elements supplied
+    /// as [`InputValue`]s are always reduced by construction and need no check. Disabled by
+    /// default.
+    pub fn reduced_fields(mut self, reduced_fields: bool) -> Self {
+        self.reduced_fields = reduced_fields;
+        self
+    }
+
+    /// Resolves each function to its highest versioned artifact in the export directory.
+    ///
+    /// Artifacts may be versioned as `{fn_name}.v{N}.json` and kept side by side, running
+    /// `fn_name` then loads the artifact with the highest `N`. An unversioned `{fn_name}.json` is
+    /// treated as version `0`. Disabled by default, in which case only `{fn_name}.json` is loaded.
+    pub fn latest_version(mut self, latest_version: bool) -> Self {
+        self.latest_version = latest_version;
+        self
+    }
+
+    /// Limits the number of Brillig instructions a run may execute before it is aborted with
+    /// [`Error::StepLimitExceeded`].
+    ///
+    /// The limit is shared by every unconstrained call in the run. Unlike a deadline, it aborts at
+    /// the same point on every machine, which guards against runaway unconstrained loops while
+    /// keeping test runs reproducible. With a limit set, programs are executed step by step. The
+    /// limit applies to every run, including those of [`NoirRunner::run_until`] and
+    /// [`NoirRunner::run_with_resource_stats`]. Defaults to unlimited.
+    pub fn max_brillig_steps(mut self, max_brillig_steps: usize) -> Self {
+        self.max_brillig_steps = Some(max_brillig_steps);
+        self
+    }
+
+    /// Controls whether the diagnostic of a failed execution is reported to stderr.
+    ///
+    /// Disable it in libraries and servers to keep diagnostics out of their logs, the assertion
+    /// message and call stack are still available through [`Error::Execution`]. Enabled by
+    /// default.
+    pub fn report_diagnostics(mut self, report_diagnostics: bool) -> Self {
+        self.report_diagnostics = report_diagnostics;
+        self
+    }
+
+    /// Sets the Barretenberg binary used by [`NoirRunner::prove`] and [`NoirRunner::verify`].
+    ///
+    /// Its version must support the program's nargo version. Defaults to `bb`, resolved through
+    /// `PATH`.
+    pub fn prover_binary(mut self, prover_binary: PathBuf) -> Self {
+        self.prover_binary = prover_binary;
+        self
+    }
+
+    /// Registers a custom encoder for the parameter with the given name.
+    ///
+    /// Whenever an input is supplied for the parameter, in any function, the encoder is called
+    /// with the supplied value and its result is encoded with the ABI in its place. Parameters
+    /// without a registered encoder use their supplied value as is. Registering an encoder for
+    /// the same parameter again replaces it.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use noir_runner::{InputValue, NoirRunner, ToNoir};
+    ///
+    /// let runner = NoirRunner::builder(std::path::PathBuf::from("tests"))
+    ///     .encoder("x", |value| match value {
+    ///         InputValue::String(hex) => Ok(u64::from_str_radix(&hex, 16).unwrap().to_noir()),
+    ///         value => Ok(value),
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn encoder<F>(mut self, param: impl Into<String>, encoder: F) -> Self
+    where
+        F: Fn(InputValue) -> Result<InputValue, Error> + Send + Sync + 'static,
+    {
+        self.encoders.insert(param.into(), Arc::new(encoder));
+        self
+    }
+
+    /// Registers a transform applied to the whole input map before every run.
+    ///
+    /// Transforms are called with the program's ABI and the input map after the per-parameter
+    /// encoders are applied, and before the inputs are validated against the ABI and encoded, so
+    /// values they add or replace are validated like any other input. Multiple transforms are
+    /// applied in registration order.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use noir_runner::{NoirRunner, ToNoir};
+    ///
+    /// let runner = NoirRunner::builder(std::path::PathBuf::from("tests"))
+    ///     .input_transform(|abi, input_map| {
+    ///         if abi.parameters.iter().any(|parameter| parameter.name == "nonce") {
+    ///             input_map.insert("nonce".to_owned(), 1u64.to_noir());
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn input_transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&Abi, &mut BTreeMap<String, InputValue>) + Send + Sync + 'static,
+    {
+        self.transforms.push(Arc::new(transform));
+        self
+    }
+
+    /// Sets how numbers are converted into field elements by [`NoirRunner::to_input_value`].
+    ///
+    /// Defaults to [`NumberStrategy::ExactInteger`], matching [`ToNoir`](crate::ToNoir).
+    pub fn number_strategy(mut self, numbers: NumberStrategy) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    /// Attempts to construct the [`NoirRunner`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the `Nargo.toml` manifest is not found or if the export directory
+    /// cannot be resolved, unless the export directory is set with
+    /// [`NoirRunnerBuilder::export_directory`].
+    pub fn build(self) -> Result<NoirRunner, Error> {
+        let from_manifest = self.export_directory.is_none();
+
+        let export_directory = match self.export_directory {
+            Some(export_directory) => export_directory,
+            None => resolve_export_directory(&self.program_dir)?,
+        };
+
+        let capacity = if self.cache_artifacts {
+            self.max_cached_artifacts
+        } else {
+            Some(0)
+        };
+
+        Ok(NoirRunner {
+            program_dir: self.program_dir,
+            export_directory,
+            artifacts: Artifacts::ExportDirectory,
+            cache: Mutex::new(LruCache::new(capacity)),
+            encoders: self.encoders,
+            results: self.memoized_results.map(ResultCache::new),
+            catch_panics: self.catch_panics,
+            reduced_fields: self.reduced_fields,
+            latest_version: self.latest_version,
+            transforms: self.transforms,
+            numbers: self.numbers,
+            max_brillig_steps: self.max_brillig_steps,
+            report_diagnostics: self.report_diagnostics,
+            prover_binary: self.prover_binary,
+            from_manifest,
+        })
+    }
+}
+
+/// Resolves the export directory of the workspace from its `Nargo.toml` manifest.
+pub(crate) fn resolve_export_directory(program_dir: &Path) -> Result<PathBuf, Error> {
+    let workspace = resolve_workspace_from_toml(
+        &get_package_manifest(program_dir).map_err(Error::NargoManifest)?,
+        PackageSelection::All,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+    )
+    .map_err(Error::NargoManifest)?;
+
+    Ok(workspace.export_directory_path())
+}
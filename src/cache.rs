@@ -0,0 +1,306 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use acvm::FieldElement;
+use acvm::acir::native_types::WitnessMap;
+use noirc_abi::input_parser::InputValue;
+
+/// Least Recently Used Cache
+///
+/// Maps keys to values, evicting the least recently used entry once the capacity is exceeded. A
+/// capacity of `None` never evicts. Pinned entries are never evicted, so the cache may hold more
+/// entries than its capacity if too many are pinned.
+#[derive(Debug)]
+pub(crate) struct LruCache<K, V> {
+    capacity: Option<usize>,
+    entries: HashMap<K, CacheEntry<V>>,
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry<V> {
+    value: V,
+    last_used: u64,
+    pinned: bool,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    pub(crate) fn new(capacity: Option<usize>) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Returns a clone of the cached value and marks it as most recently used.
+    pub(crate) fn get<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.clock += 1;
+
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = self.clock;
+
+        Some(entry.value.clone())
+    }
+
+    /// Inserts a value as most recently used, evicting the least recently used unpinned entries if
+    /// the capacity is exceeded. Replacing a pinned entry keeps it pinned.
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        let pinned = self.entries.get(&key).is_some_and(|entry| entry.pinned);
+
+        self.insert_entry(key, value, pinned);
+    }
+
+    /// Inserts a value as most recently used and exempts it from eviction, returning `false` if it
+    /// did not survive the eviction of the remaining entries.
+    ///
+    /// The entry is pinned before evicting, so it is kept even if the capacity is `0` or already
+    /// taken by pinned entries.
+    pub(crate) fn insert_pinned(&mut self, key: K, value: V) -> bool {
+        self.insert_entry(key.clone(), value, true);
+
+        self.entries.contains_key(&key)
+    }
+
+    fn insert_entry(&mut self, key: K, value: V, pinned: bool) {
+        self.clock += 1;
+
+        self.entries.insert(
+            key,
+            CacheEntry {
+                value,
+                last_used: self.clock,
+                pinned,
+            },
+        );
+
+        while self
+            .capacity
+            .is_some_and(|capacity| self.entries.len() > capacity)
+        {
+            let Some(lru_key) = self
+                .entries
+                .iter()
+                .filter(|(_, entry)| !entry.pinned)
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+
+            self.entries.remove(&lru_key);
+        }
+    }
+
+    /// Exempts an entry from eviction, returning `false` if the key is not cached.
+    pub(crate) fn pin<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.set_pinned(key, true)
+    }
+
+    /// Makes a pinned entry evictable again, returning `false` if the key is not cached.
+    pub(crate) fn unpin<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.set_pinned(key, false)
+    }
+
+    fn set_pinned<Q>(&mut self, key: &Q, pinned: bool) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(entry) = self.entries.get_mut(key) else {
+            return false;
+        };
+
+        entry.pinned = pinned;
+
+        true
+    }
+
+    /// Removes every entry, including pinned entries.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.contains_key(key)
+    }
+}
+
+/// The key of a memoized run, the program hash and the encoded initial witness.
+type ResultKey = (u64, Vec<(u32, FieldElement)>);
+
+/// Result Cache
+///
+/// Memoizes decoded run results by program and encoded inputs, counting cache hits.
+#[derive(Debug)]
+pub(crate) struct ResultCache {
+    results: Mutex<LruCache<ResultKey, Option<InputValue>>>,
+    hits: AtomicUsize,
+}
+
+impl ResultCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            results: Mutex::new(LruCache::new(Some(capacity))),
+            hits: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.results().capacity().unwrap_or(0)
+    }
+
+    /// Builds the key of a run from the program hash and its encoded initial witness.
+    pub(crate) fn key(program_hash: u64, initial_witness: &WitnessMap<FieldElement>) -> ResultKey {
+        let witness = initial_witness
+            .clone()
+            .into_iter()
+            .map(|(witness, value)| (witness.witness_index(), value))
+            .collect();
+
+        (program_hash, witness)
+    }
+
+    /// Returns the memoized result of a run, counting a hit if one is found.
+    pub(crate) fn get(&self, key: &ResultKey) -> Option<Option<InputValue>> {
+        let result = self.results().get(key)?;
+        self.hits.fetch_add(1, Ordering::Relaxed);
+
+        Some(result)
+    }
+
+    pub(crate) fn insert(&self, key: ResultKey, result: Option<InputValue>) {
+        self.results().insert(key, result);
+    }
+
+    pub(crate) fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    fn results(&self) -> MutexGuard<'_, LruCache<ResultKey, Option<InputValue>>> {
+        // the cache is never left in an inconsistent state, so a poisoned lock is recovered
+        self.results.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unbounded() {
+        let mut cache = LruCache::new(None);
+
+        for i in 0..100 {
+            cache.insert(i.to_string(), i);
+        }
+
+        assert!((0..100).all(|i| cache.contains(&i.to_string())));
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = LruCache::new(Some(2));
+
+        cache.insert("a".to_owned(), 1);
+        cache.insert("b".to_owned(), 2);
+
+        assert_eq!(cache.get("a"), Some(1));
+
+        cache.insert("c".to_owned(), 3);
+
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("b"));
+        assert!(cache.contains("c"));
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut cache = LruCache::new(None);
+
+        cache.insert("a".to_owned(), 1);
+        cache.insert("b".to_owned(), 2);
+        cache.pin("a");
+
+        cache.clear();
+
+        assert!(!cache.contains("a"));
+        assert!(!cache.contains("b"));
+    }
+
+    #[test]
+    fn test_pinned_survives_eviction() {
+        let mut cache = LruCache::new(Some(2));
+
+        cache.insert("a".to_owned(), 1);
+        assert!(cache.pin("a"));
+
+        for i in 0..10 {
+            cache.insert(i.to_string(), i);
+        }
+
+        assert!(cache.contains("a"));
+        assert!(cache.contains("9"));
+        assert!(!cache.contains("8"));
+
+        assert!(cache.unpin("a"));
+        cache.insert("b".to_owned(), 2);
+
+        assert!(!cache.contains("a"));
+        assert!(!cache.pin("a"));
+    }
+
+    #[test]
+    fn test_insert_pinned() {
+        let mut cache = LruCache::new(Some(0));
+
+        cache.insert("a".to_owned(), 1);
+
+        assert!(!cache.contains("a"));
+
+        assert!(cache.insert_pinned("a".to_owned(), 1));
+        assert!(cache.insert_pinned("b".to_owned(), 2));
+
+        cache.insert("c".to_owned(), 3);
+
+        assert!(cache.contains("a"));
+        assert!(cache.contains("b"));
+        assert!(!cache.contains("c"));
+    }
+
+    #[test]
+    fn test_result_cache_counts_hits() {
+        let cache = ResultCache::new(1);
+        let key = ResultCache::key(0, &WitnessMap::default());
+
+        assert_eq!(cache.get(&key), None);
+
+        cache.insert(key.clone(), Some(InputValue::Field(1u32.into())));
+
+        assert_eq!(cache.get(&key), Some(Some(InputValue::Field(1u32.into()))));
+        assert_eq!(cache.hits(), 1);
+    }
+}
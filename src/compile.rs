@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use crate::Error;
+
+use nargo::parse_all;
+use noirc_driver::{
+    CompileOptions, CompiledProgram, check_crate, compile_no_check, file_manager_with_stdlib,
+    prepare_crate,
+};
+use noirc_errors::FileDiagnostic;
+use noirc_frontend::hir::Context;
+
+/// The in-memory path the compiled source is registered under.
+const SOURCE_FILE: &str = "main.nr";
+
+/// Compiles a function of a single Noir source file in memory.
+///
+/// The function is looked up among the `#[export]` functions of the source, `main` may be
+/// compiled as well. Only the standard library is available as a dependency.
+pub(crate) fn compile_function(source: &str, fn_name: &str) -> Result<CompiledProgram, Error> {
+    let file_name = Path::new(SOURCE_FILE);
+
+    let mut file_manager = file_manager_with_stdlib(Path::new(""));
+    file_manager
+        .add_file_with_source(file_name, source.to_owned())
+        .ok_or_else(|| Error::Compile(format!("failed to add `{SOURCE_FILE}`")))?;
+
+    let parsed_files = parse_all(&file_manager);
+
+    let mut context = Context::new(file_manager, parsed_files);
+    let crate_id = prepare_crate(&mut context, file_name);
+
+    let options = CompileOptions::default();
+
+    check_crate(&mut context, crate_id, &options).map_err(compile_error)?;
+
+    let mut functions = context.get_all_exported_functions_in_crate(&crate_id);
+
+    if let Some(main) = context.get_main_function(&crate_id) {
+        functions.push(("main".to_owned(), main));
+    }
+
+    let Some(function_id) = functions
+        .iter()
+        .find(|(name, _)| name == fn_name)
+        .map(|(_, function_id)| *function_id)
+    else {
+        return Err(Error::FunctionNotFound {
+            name: fn_name.to_owned(),
+            available: functions.into_iter().map(|(name, _)| name).collect(),
+        });
+    };
+
+    compile_no_check(&mut context, &options, function_id, None, false)
+        .map_err(|err| compile_error(vec![err.into()]))
+}
+
+/// Renders the error diagnostics of a failed compilation, warnings are left out.
+fn compile_error(diagnostics: Vec<FileDiagnostic>) -> Error {
+    let messages = diagnostics
+        .into_iter()
+        .filter(|diagnostic| diagnostic.diagnostic.is_error())
+        .map(|diagnostic| diagnostic.diagnostic.message)
+        .collect::<Vec<_>>();
+
+    Error::Compile(messages.join("\n"))
+}
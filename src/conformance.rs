@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+
+use crate::signed::decode_signed;
+use crate::{Error, NoirRunner};
+
+use acvm::FieldElement;
+use acvm::acir::native_types::WitnessStack;
+use bn254_blackbox_solver::Bn254BlackBoxSolver;
+use nargo_toml::{PackageSelection, get_package_manifest, resolve_workspace_from_toml};
+use noirc_abi::input_parser::{Format, InputValue};
+use noirc_artifacts::program::ProgramArtifact;
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+
+/// The name used for the prover file and witness written to the package.
+const CONFORMANCE_NAME: &str = "noir_runner_conformance";
+
+/// Conformance Report
+///
+/// The decoded outputs of the runner and of `nargo execute` for the same inputs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConformanceReport {
+    /// The output decoded by the runner.
+    pub runner_output: Option<InputValue>,
+    /// The output decoded from the witness written by `nargo execute`.
+    pub nargo_output: Option<InputValue>,
+}
+
+impl ConformanceReport {
+    /// Returns `true` if the runner and `nargo execute` decoded the same output.
+    pub fn is_conformant(&self) -> bool {
+        self.runner_output == self.nargo_output
+    }
+}
+
+/// Runs a function with the runner and the equivalent binary package with `nargo execute`, and
+/// reports both decoded outputs.
+///
+/// This is conformance testing against `nargo execute`, it requires a `nargo` binary on the
+/// `PATH`.
+///
+/// Exported functions cannot be executed by `nargo` directly, as such `package_dir` must contain a
+/// binary package whose `main` takes the same parameters as the function and returns its output.
+/// The inputs are written to `noir_runner_conformance.toml` in the package and the witness to
+/// `noir_runner_conformance.gz` in its target directory, leaving any `Prover.toml` untouched. The
+/// inputs are written as the runner encodes them, after its encoders and transforms, and signed
+/// outputs are decoded the same way for both.
+///
+/// ## Arguments
+///
+/// - `runner`: The runner to execute the function with.
+/// - `fn_name`: The name of the exported function to run.
+/// - `input_map`: A map of input values to pass to the function.
+/// - `package_dir`: The root directory of the equivalent binary package.
+///
+/// ## Errors
+///
+/// Returns an error if the runner fails, `nargo` cannot be spawned or exits unsuccessfully, or the
+/// package's artifact or witness cannot be read.
+pub fn compare_with_nargo(
+    runner: &NoirRunner,
+    fn_name: &str,
+    input_map: BTreeMap<String, InputValue>,
+    package_dir: &Path,
+) -> Result<ConformanceReport, Error> {
+    let program = runner.load_program(fn_name)?;
+    let initial_witness = runner.encode_inputs(&program, input_map)?;
+
+    // nargo is given the inputs as the runner encoded them, so both execute the same witness
+    let (prover_inputs, _) = program.abi.decode(&initial_witness).map_err(Error::Abi)?;
+
+    let runner_output = runner.solve(&program, initial_witness, &Bn254BlackBoxSolver(false))?;
+
+    nargo(package_dir, &["compile"])?;
+
+    let workspace = resolve_workspace_from_toml(
+        &get_package_manifest(package_dir).map_err(Error::NargoManifest)?,
+        PackageSelection::All,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+    )
+    .map_err(Error::NargoManifest)?;
+
+    let package = workspace
+        .members
+        .first()
+        .ok_or_else(|| Error::Nargo(format!("no package found in {}", package_dir.display())))?;
+
+    let target_dir = workspace.target_directory_path();
+
+    let artifact_path = target_dir.join(format!("{}.json", package.name));
+    let artifact: ProgramArtifact =
+        serde_json::from_slice(&std::fs::read(artifact_path).map_err(Error::Io)?)
+            .map_err(Error::Serde)?;
+
+    let prover_toml = Format::Toml
+        .serialize(&prover_inputs, &artifact.abi)
+        .map_err(|err| Error::Nargo(format!("{err:?}")))?;
+
+    std::fs::write(
+        package_dir.join(format!("{CONFORMANCE_NAME}.toml")),
+        prover_toml,
+    )
+    .map_err(Error::Io)?;
+
+    nargo(
+        package_dir,
+        &[
+            "execute",
+            CONFORMANCE_NAME,
+            "--prover-name",
+            CONFORMANCE_NAME,
+        ],
+    )?;
+
+    let witness_bytes =
+        std::fs::read(target_dir.join(format!("{CONFORMANCE_NAME}.gz"))).map_err(Error::Io)?;
+
+    let witness_stack = WitnessStack::<FieldElement>::try_from(witness_bytes.as_slice())
+        .map_err(|err| Error::Nargo(format!("{err:?}")))?;
+
+    let mut nargo_output = witness_stack
+        .peek()
+        .map(|item| artifact.abi.decode(&item.witness).map_err(Error::Abi))
+        .transpose()?
+        .and_then(|(_, return_value)| return_value);
+
+    if let (Some(return_type), Some(nargo_output)) = (&artifact.abi.return_type, &mut nargo_output)
+    {
+        decode_signed(&return_type.abi_type, nargo_output);
+    }
+
+    Ok(ConformanceReport {
+        runner_output,
+        nargo_output,
+    })
+}
+
+fn nargo(package_dir: &Path, args: &[&str]) -> Result<(), Error> {
+    let output = Command::new("nargo")
+        .args(args)
+        .arg("--program-dir")
+        .arg(package_dir)
+        .output()
+        .map_err(Error::Io)?;
+
+    if !output.status.success() {
+        return Err(Error::Nargo(
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ));
+    }
+
+    Ok(())
+}
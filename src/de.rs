@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+
+use crate::Error;
+use crate::option::{PRESENCE_FIELD, VALUE_FIELD};
+use crate::validation::variant_name;
+
+use acvm::{AcirField, FieldElement};
+use noirc_abi::input_parser::InputValue;
+use serde::de::value::{
+    Error as DeserializeError, MapAccessDeserializer, MapDeserializer, SeqDeserializer,
+    StringDeserializer,
+};
+use serde::de::{self, DeserializeOwned, Error as _, IntoDeserializer, Visitor};
+
+/// Deserializes a Rust value from an [`InputValue`].
+///
+/// This decodes any type implementing [`Deserialize`](serde::Deserialize), such as structs with
+/// `#[derive(Deserialize)]`, and mirrors the conventions of [`ToNoir`](crate::ToNoir): fields
+/// become integers, vectors become sequences, structs become structs or maps, and strings become
+/// strings or unit enum variants. Options follow the presence-flag convention, a
+/// `{ has_value, value }` struct deserializes to `None` if `has_value` is `0` and to `Some` of
+/// `value` if it is `1`.
+///
+/// Fields are read as integers as `ToNoir` writes them, so negative integers are those within
+/// `i128` of the field modulus. Fields too large for an integer are read as hex strings.
+///
+/// ## Errors
+///
+/// Returns [`Error::InvalidOutput`] if the value does not match the shape of `T`.
+///
+/// ## Example
+///
+/// ```rust
+/// use noir_runner::{ToNoir, from_input_value};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Pair {
+///     a: u64,
+///     b: u64,
+/// }
+///
+/// let value = Pair { a: 1, b: 2 }.to_noir();
+///
+/// assert_eq!(from_input_value::<Pair>(value).unwrap(), Pair { a: 1, b: 2 });
+/// ```
+pub fn from_input_value<T: DeserializeOwned>(value: InputValue) -> Result<T, Error> {
+    T::deserialize(InputValueDeserializer(value))
+        .map_err(|err| Error::InvalidOutput(err.to_string()))
+}
+
+/// Reads a field element as a signed integer, treating values within `i128` of the modulus as
+/// negative.
+pub(crate) fn field_to_i128(field: FieldElement) -> Option<i128> {
+    if field.num_bits() < 128 {
+        return Some(field.to_u128() as i128);
+    }
+
+    let magnitude = -field;
+
+    (magnitude.num_bits() <= 128 && magnitude.to_u128() <= 1 << 127)
+        .then(|| (magnitude.to_u128() as i128).wrapping_neg())
+}
+
+struct InputValueDeserializer(InputValue);
+
+impl<'de> IntoDeserializer<'de, DeserializeError> for InputValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl InputValueDeserializer {
+    fn into_field(self) -> Result<FieldElement, DeserializeError> {
+        match self.0 {
+            InputValue::Field(field) => Ok(field),
+            value => Err(DeserializeError::custom(format!(
+                "expected a Field, found a {}",
+                variant_name(&value)
+            ))),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for InputValueDeserializer {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        match self.0 {
+            InputValue::Field(field) if field.num_bits() <= 64 => {
+                visitor.visit_u64(field.to_u128() as u64)
+            }
+            InputValue::Field(field) => match field_to_i128(field) {
+                Some(value) if i64::try_from(value).is_ok() => visitor.visit_i64(value as i64),
+                Some(value) if value >= 0 => visitor.visit_u128(value as u128),
+                Some(value) => visitor.visit_i128(value),
+                None => visitor.visit_string(field.to_hex()),
+            },
+            InputValue::String(string) => visitor.visit_string(string),
+            InputValue::Vec(values) => {
+                let values = values.into_iter().map(InputValueDeserializer);
+
+                visitor.visit_seq(SeqDeserializer::new(values))
+            }
+            InputValue::Struct(fields) => visitor.visit_map(struct_deserializer(fields)),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        let field = self.into_field()?;
+
+        match field {
+            field if field.is_zero() => visitor.visit_bool(false),
+            field if field.is_one() => visitor.visit_bool(true),
+            field => Err(DeserializeError::custom(format!(
+                "expected a boolean, found {field}"
+            ))),
+        }
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        let field = self.into_field()?;
+
+        let value = field_to_i128(field)
+            .ok_or_else(|| DeserializeError::custom(format!("expected an i128, found {field}")))?;
+
+        visitor.visit_i128(value)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        let field = self.into_field()?;
+
+        if field.num_bits() > 128 {
+            return Err(DeserializeError::custom(format!(
+                "expected a u128, found {field}"
+            )));
+        }
+
+        visitor.visit_u128(field.to_u128())
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        let field = self.into_field()?;
+
+        let value = (field.num_bits() <= 32)
+            .then(|| char::from_u32(field.to_u128() as u32))
+            .flatten()
+            .ok_or_else(|| DeserializeError::custom(format!("expected a char, found {field}")))?;
+
+        visitor.visit_char(value)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        let mut fields = match self.0 {
+            InputValue::Struct(fields) => fields,
+            value => {
+                return Err(DeserializeError::custom(format!(
+                    "expected a Struct, found a {}",
+                    variant_name(&value)
+                )));
+            }
+        };
+
+        let Some(has_value) = fields.remove(PRESENCE_FIELD) else {
+            return Err(DeserializeError::missing_field(PRESENCE_FIELD));
+        };
+
+        let has_value: bool = de::Deserialize::deserialize(InputValueDeserializer(has_value))?;
+
+        if !has_value {
+            return visitor.visit_none();
+        }
+
+        match fields.remove(VALUE_FIELD) {
+            Some(value) => visitor.visit_some(InputValueDeserializer(value)),
+            None => Err(DeserializeError::missing_field(VALUE_FIELD)),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        match self.0 {
+            InputValue::String(variant) => {
+                visitor.visit_enum(StringDeserializer::<DeserializeError>::new(variant))
+            }
+            InputValue::Struct(fields) if fields.len() == 1 => {
+                visitor.visit_enum(MapAccessDeserializer::new(struct_deserializer(fields)))
+            }
+            value => Err(DeserializeError::custom(format!(
+                "expected an enum variant, found a {}",
+                variant_name(&value)
+            ))),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 str string bytes byte_buf seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Deserializes the fields of a struct as a map from field names to values.
+fn struct_deserializer<'de>(
+    fields: BTreeMap<String, InputValue>,
+) -> MapDeserializer<'de, impl Iterator<Item = (String, InputValueDeserializer)>, DeserializeError>
+{
+    let fields = fields
+        .into_iter()
+        .map(|(name, value)| (name, InputValueDeserializer(value)));
+
+    MapDeserializer::new(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ToNoir;
+
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn test_round_trip_struct() {
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        struct Test {
+            a: u32,
+            b: i64,
+            c: String,
+            d: Vec<bool>,
+            e: Option<u8>,
+            f: Option<u8>,
+        }
+
+        let value = Test {
+            a: 1,
+            b: -5,
+            c: "hello".to_owned(),
+            d: vec![true, false],
+            e: Some(7),
+            f: None,
+        };
+
+        assert_eq!(
+            from_input_value::<Test>(value.clone().to_noir()).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn test_field_to_i128() {
+        assert_eq!(field_to_i128(5u32.into()), Some(5));
+        assert_eq!(field_to_i128(-FieldElement::from(5u32)), Some(-5));
+        assert_eq!(
+            field_to_i128(-FieldElement::from(1u128 << 127)),
+            Some(i128::MIN)
+        );
+
+        let large = FieldElement::from(2u128).pow(&FieldElement::from(200u128));
+
+        assert_eq!(field_to_i128(large), None);
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let result = from_input_value::<BTreeMap<String, u32>>(1u32.to_noir());
+
+        assert!(matches!(result, Err(Error::InvalidOutput(_))));
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use crate::Error;
+
+use noirc_abi::Abi;
+use noirc_abi::input_parser::InputValue;
+
+/// A custom encoder for a single parameter.
+///
+/// The encoder receives the input value supplied for the parameter and returns the value that is
+/// encoded with the ABI in its place.
+pub type InputEncoder = Arc<dyn Fn(InputValue) -> Result<InputValue, Error> + Send + Sync>;
+
+/// Registry of custom encoders keyed by parameter name.
+#[derive(Clone, Default)]
+pub(crate) struct InputEncoders(BTreeMap<String, InputEncoder>);
+
+impl InputEncoders {
+    pub(crate) fn insert(&mut self, param: String, encoder: InputEncoder) {
+        self.0.insert(param, encoder);
+    }
+
+    /// Replaces each input that has a registered encoder with its encoded value.
+    pub(crate) fn apply(&self, input_map: &mut BTreeMap<String, InputValue>) -> Result<(), Error> {
+        for (param, encoder) in &self.0 {
+            if let Some(value) = input_map.remove(param) {
+                input_map.insert(param.clone(), encoder(value)?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for InputEncoders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+/// A transform applied to the whole input map before it is encoded.
+///
+/// The transform receives the program's ABI, so it can be type-aware, and may add, remove or
+/// replace inputs in place.
+pub type InputTransform = Arc<dyn Fn(&Abi, &mut BTreeMap<String, InputValue>) + Send + Sync>;
+
+/// Registry of input transforms, applied in registration order.
+#[derive(Clone, Default)]
+pub(crate) struct InputTransforms(Vec<InputTransform>);
+
+impl InputTransforms {
+    pub(crate) fn push(&mut self, transform: InputTransform) {
+        self.0.push(transform);
+    }
+
+    pub(crate) fn apply(&self, abi: &Abi, input_map: &mut BTreeMap<String, InputValue>) {
+        for transform in &self.0 {
+            transform(abi, input_map);
+        }
+    }
+}
+
+impl Debug for InputTransforms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "InputTransforms({})", self.0.len())
+    }
+}
@@ -1,3 +1,9 @@
+use std::fmt::{self, Display};
+
+use crate::runner::release;
+
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+
 /// NoirRunner Errors
 ///
 /// This encapsulates all possible errors that can occur when using the `NoirRunner` struct.
@@ -7,20 +13,250 @@ pub enum Error {
     NargoManifest(nargo_toml::ManifestError),
     /// A file could not be read from the file system.
     Io(std::io::Error),
+    /// The function has no artifact, holding the names of the functions that do.
+    FunctionNotFound {
+        name: String,
+        available: Vec<String>,
+    },
     /// An error occurred while deserializing JSON data.
     ///
     /// Possible causes:
     ///
-    /// - The nargo version is not compatible with the runner (v0.36.0)
+    /// - The nargo version is not compatible with the runner, refer to [`Error::VersionMismatch`]
     /// - The program has not been exported (`nargo export`)
     Serde(serde_json::Error),
+    /// The artifact was compiled with a different nargo version than the runner supports.
+    ///
+    /// Both versions are releases, such as `1.0.0-beta.3`, without build metadata. Re-export the
+    /// program with the `expected` version of nargo.
+    VersionMismatch { expected: String, found: String },
+    /// A NaN or infinite float cannot be converted into a field element.
+    NonFiniteFloat(f64),
+    /// A value cannot be converted into an input value, such as a float rejected by the
+    /// [`NumberStrategy`] or a map with non-string keys.
+    ///
+    /// [`NumberStrategy`]: crate::NumberStrategy
+    InvalidValue(String),
+    /// A string is not a valid field element, or is not below the field modulus.
+    InvalidField(String),
     /// An error occurred while parsing the ABI.
     ///
     /// This may happen with the input or output of a program.
     Abi(noirc_abi::errors::AbiError),
+    /// The program's ABI does not have the shape required by the called method.
+    AbiMismatch(String),
+    /// An input value does not have the variant expected by its ABI type.
+    ///
+    /// The `param` is the path to the mismatched value, such as `pair.a` or `values[2]`.
+    TypeMismatch {
+        param: String,
+        expected: String,
+        found: String,
+    },
+    /// An array, tuple or string input does not have the length declared by its ABI type.
+    ///
+    /// The `param` is the path to the mismatched value, as with [`Error::TypeMismatch`].
+    LengthMismatch {
+        param: String,
+        expected: usize,
+        found: usize,
+    },
+    /// An input was supplied for a parameter the program does not have.
+    UnknownParameter(String),
+    /// An input value could not be parsed for its parameter.
+    InvalidInput { param: String, reason: String },
+    /// An output value does not have the variant required by the requested Rust type.
+    ReturnTypeMismatch { expected: String, found: String },
+    /// An output value could not be decoded into the requested Rust type.
+    InvalidOutput(String),
+    /// A field element does not fit in the `target` integer type, such as `u64`.
+    ValueOutOfRange { value: String, target: String },
+    /// Execution did not complete before its deadline.
+    Timeout,
+    /// Execution exceeded its Brillig step limit, refer to
+    /// [`NoirRunnerBuilder::max_brillig_steps`].
+    ///
+    /// [`NoirRunnerBuilder::max_brillig_steps`]: crate::NoirRunnerBuilder::max_brillig_steps
+    StepLimitExceeded,
+    /// A run of [`NoirRunner::run_batch`] failed for the input map at `index`.
+    ///
+    /// [`NoirRunner::run_batch`]: crate::NoirRunner::run_batch
+    Batch { index: usize, error: Box<Error> },
+    /// The Barretenberg prover could not be run or failed, holding its error output.
+    Prover(String),
+    /// Noir source failed to compile, holding the compiler's error messages, refer to
+    /// [`NoirRunner::compile_and_run`].
+    ///
+    /// [`NoirRunner::compile_and_run`]: crate::NoirRunner::compile_and_run
+    Compile(String),
+    /// Executing the program failed, such as on an unsatisfied constraint.
+    Execution {
+        /// The debug representation of nargo's error.
+        message: String,
+        /// The assertion message of the failed constraint, if it has one.
+        assertion: Option<String>,
+        /// The opcode locations of the call stack at the failure, outermost first, as
+        /// `function:opcode` where `function` is the index of the ACIR function.
+        call_stack: Vec<String>,
+    },
     /// An error occurred while executing the program.
     ///
     /// Note that we run diagnostics at runtime, as such we convert this error to a string using the
     /// `Debug` trait to avoid generic type parameters.
     Nargo(String),
+    /// Execution panicked, refer to [`NoirRunnerBuilder::catch_panics`].
+    ///
+    /// [`NoirRunnerBuilder::catch_panics`]: crate::NoirRunnerBuilder::catch_panics
+    Panic {
+        /// The panic message, if the payload was a string.
+        message: String,
+    },
+}
+
+impl Error {
+    /// Returns the message of the failed assertion, such as `"x must be zero"` for a failed
+    /// `assert(x == 0, "x must be zero")`, if the error is an [`Error::Execution`] with one.
+    ///
+    /// The error of a failing input map of [`NoirRunner::run_batch`] is looked through.
+    ///
+    /// [`NoirRunner::run_batch`]: crate::NoirRunner::run_batch
+    pub fn assertion_message(&self) -> Option<&str> {
+        match self {
+            Self::Execution { assertion, .. } => assertion.as_deref(),
+            Self::Batch { error, .. } => error.assertion_message(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NargoManifest(err) => write!(f, "failed to read the Nargo.toml manifest: {err}"),
+            Self::Io(err) => write!(f, "failed to read from the file system: {err}"),
+            Self::Serde(err) => write!(
+                f,
+                "failed to deserialize JSON, check that the program is exported with nargo {}: \
+                 {err}",
+                release(NOIR_ARTIFACT_VERSION_STRING)
+            ),
+            Self::FunctionNotFound { name, available } => write!(
+                f,
+                "function `{name}` not found, available functions: [{}]",
+                available.join(", ")
+            ),
+            Self::VersionMismatch { expected, found } => write!(
+                f,
+                "artifact was compiled with nargo {found}, but the runner supports nargo {expected}"
+            ),
+            Self::NonFiniteFloat(value) => {
+                write!(
+                    f,
+                    "{value} is not a finite number and cannot be a field element"
+                )
+            }
+            Self::InvalidValue(reason) => write!(f, "invalid value: {reason}"),
+            Self::InvalidField(reason) => write!(f, "invalid field element: {reason}"),
+            Self::Abi(err) => write!(f, "ABI error: {err}"),
+            Self::AbiMismatch(reason) => write!(f, "ABI mismatch: {reason}"),
+            Self::TypeMismatch {
+                param,
+                expected,
+                found,
+            } => write!(f, "input `{param}` expected a {expected}, found a {found}"),
+            Self::LengthMismatch {
+                param,
+                expected,
+                found,
+            } => write!(
+                f,
+                "input `{param}` expected a length of {expected}, found {found}"
+            ),
+            Self::UnknownParameter(param) => write!(f, "unknown parameter `{param}`"),
+            Self::InvalidInput { param, reason } => write!(f, "invalid input `{param}`: {reason}"),
+            Self::ReturnTypeMismatch { expected, found } => {
+                write!(f, "output expected a {expected}, found a {found}")
+            }
+            Self::InvalidOutput(reason) => write!(f, "invalid output: {reason}"),
+            Self::ValueOutOfRange { value, target } => {
+                write!(f, "{value} does not fit in a {target}")
+            }
+            Self::Timeout => f.write_str("execution did not complete before its deadline"),
+            Self::StepLimitExceeded => f.write_str("execution exceeded its Brillig step limit"),
+            Self::Batch { index, error } => write!(f, "input map {index} failed: {error}"),
+            Self::Prover(reason) => write!(f, "prover failed: {reason}"),
+            Self::Compile(reason) => write!(f, "compilation failed: {reason}"),
+            Self::Execution {
+                message,
+                assertion,
+                call_stack,
+            } => {
+                match assertion {
+                    Some(assertion) => write!(f, "execution failed: {assertion}")?,
+                    None => write!(f, "execution failed: {message}")?,
+                }
+
+                match call_stack.last() {
+                    Some(location) => write!(f, " at opcode {location}"),
+                    None => Ok(()),
+                }
+            }
+            Self::Nargo(err) => write!(f, "execution failed: {err}"),
+            Self::Panic { message } => write!(f, "execution panicked: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NargoManifest(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::Serde(err) => Some(err),
+            Self::Abi(err) => Some(err),
+            Self::Batch { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::error::Error as _;
+
+    #[test]
+    fn test_display() {
+        let err = Error::UnknownParameter("x".to_owned());
+
+        assert_eq!(err.to_string(), "unknown parameter `x`");
+        assert!(err.source().is_none());
+
+        let err = Error::Execution {
+            message: "AssertionFailed".to_owned(),
+            assertion: Some("x must be zero".to_owned()),
+            call_stack: vec!["0:1".to_owned()],
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "execution failed: x must be zero at opcode 0:1"
+        );
+
+        let err = Error::Serde(serde_json::from_str::<u32>("x").unwrap_err());
+
+        assert!(
+            err.to_string()
+                .contains(&format!("nargo {}", release(NOIR_ARTIFACT_VERSION_STRING)))
+        );
+    }
+
+    #[test]
+    fn test_source() {
+        let err = Error::Io(std::io::Error::new(std::io::ErrorKind::NotFound, "missing"));
+
+        assert_eq!(err.source().unwrap().to_string(), "missing");
+        assert!(Error::Nargo("failed".to_owned()).source().is_none());
+    }
 }
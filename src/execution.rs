@@ -0,0 +1,402 @@
+use std::mem::size_of;
+use std::time::Instant;
+
+use crate::Error;
+
+use acvm::FieldElement;
+use acvm::acir::brillig::MemoryValue;
+use acvm::acir::circuit::{OpcodeLocation, Program, ResolvedOpcodeLocation};
+use acvm::acir::native_types::{WitnessMap, WitnessStack};
+use acvm::pwg::{
+    ACVM, ACVMStatus, ErrorLocation, OpcodeResolutionError, StepResult,
+    brillig::BrilligSolverStatus,
+};
+use nargo::NargoError;
+use nargo::errors::ExecutionError;
+
+pub use acvm::BlackBoxFunctionSolver;
+pub use bn254_blackbox_solver::Bn254BlackBoxSolver;
+pub use nargo::foreign_calls::ForeignCallExecutor;
+
+/// Resource Statistics
+///
+/// High-water marks observed while executing a program.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceStats {
+    /// The number of witnesses held by the solver at its peak (exact).
+    ///
+    /// Solved witness maps are retained in the witness stack until execution completes, so this is
+    /// the total number of witnesses across every circuit in the program.
+    pub peak_witness_count: usize,
+    /// The largest number of memory slots used by a single Brillig call (exact).
+    pub peak_brillig_memory_slots: usize,
+    /// The largest amount of memory used by a single Brillig call in bytes (estimated).
+    ///
+    /// This is derived from the slot count and the in-memory size of a Brillig memory value, it
+    /// does not account for allocator overhead or the VM's own bookkeeping.
+    pub peak_brillig_memory_bytes: usize,
+    /// The number of Brillig instructions executed, across every Brillig call (exact).
+    pub brillig_steps: usize,
+}
+
+/// Observes the execution loop.
+///
+/// Each method is called between solver steps. Returning an error aborts execution.
+pub(crate) trait ExecutionHook {
+    /// Called after each ACIR opcode is executed.
+    fn on_acir_step(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called after a circuit in the program has been fully solved.
+    fn on_circuit_solved(&mut self, _witness: &WitnessMap<FieldElement>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called after each Brillig instruction is executed.
+    fn on_brillig_step(&mut self, _memory: &[MemoryValue<FieldElement>]) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<H: ExecutionHook + ?Sized> ExecutionHook for &mut H {
+    fn on_acir_step(&mut self) -> Result<(), Error> {
+        (**self).on_acir_step()
+    }
+
+    fn on_circuit_solved(&mut self, witness: &WitnessMap<FieldElement>) -> Result<(), Error> {
+        (**self).on_circuit_solved(witness)
+    }
+
+    fn on_brillig_step(&mut self, memory: &[MemoryValue<FieldElement>]) -> Result<(), Error> {
+        (**self).on_brillig_step(memory)
+    }
+}
+
+impl<H: ExecutionHook> ExecutionHook for Option<H> {
+    fn on_acir_step(&mut self) -> Result<(), Error> {
+        self.as_mut().map_or(Ok(()), H::on_acir_step)
+    }
+
+    fn on_circuit_solved(&mut self, witness: &WitnessMap<FieldElement>) -> Result<(), Error> {
+        self.as_mut()
+            .map_or(Ok(()), |hook| hook.on_circuit_solved(witness))
+    }
+
+    fn on_brillig_step(&mut self, memory: &[MemoryValue<FieldElement>]) -> Result<(), Error> {
+        self.as_mut()
+            .map_or(Ok(()), |hook| hook.on_brillig_step(memory))
+    }
+}
+
+/// Calls both hooks in order, aborting with the first error.
+impl<A: ExecutionHook, B: ExecutionHook> ExecutionHook for (A, B) {
+    fn on_acir_step(&mut self) -> Result<(), Error> {
+        self.0.on_acir_step()?;
+        self.1.on_acir_step()
+    }
+
+    fn on_circuit_solved(&mut self, witness: &WitnessMap<FieldElement>) -> Result<(), Error> {
+        self.0.on_circuit_solved(witness)?;
+        self.1.on_circuit_solved(witness)
+    }
+
+    fn on_brillig_step(&mut self, memory: &[MemoryValue<FieldElement>]) -> Result<(), Error> {
+        self.0.on_brillig_step(memory)?;
+        self.1.on_brillig_step(memory)
+    }
+}
+
+impl ExecutionHook for ResourceStats {
+    fn on_circuit_solved(&mut self, witness: &WitnessMap<FieldElement>) -> Result<(), Error> {
+        self.peak_witness_count += witness.clone().into_iter().count();
+
+        Ok(())
+    }
+
+    fn on_brillig_step(&mut self, memory: &[MemoryValue<FieldElement>]) -> Result<(), Error> {
+        self.brillig_steps += 1;
+        self.peak_brillig_memory_slots = self.peak_brillig_memory_slots.max(memory.len());
+        self.peak_brillig_memory_bytes =
+            self.peak_brillig_memory_slots * size_of::<MemoryValue<FieldElement>>();
+
+        Ok(())
+    }
+}
+
+/// Aborts execution with [`Error::Timeout`] once the deadline has passed.
+pub(crate) struct Deadline(pub(crate) Instant);
+
+impl Deadline {
+    fn check(&self) -> Result<(), Error> {
+        if Instant::now() > self.0 {
+            return Err(Error::Timeout);
+        }
+
+        Ok(())
+    }
+}
+
+impl ExecutionHook for Deadline {
+    fn on_acir_step(&mut self) -> Result<(), Error> {
+        self.check()
+    }
+
+    fn on_brillig_step(&mut self, _memory: &[MemoryValue<FieldElement>]) -> Result<(), Error> {
+        self.check()
+    }
+}
+
+/// Aborts execution with [`Error::StepLimitExceeded`] once more than the given number of Brillig
+/// instructions have been executed, across every Brillig call in the program.
+pub(crate) struct StepLimit {
+    pub(crate) remaining: usize,
+}
+
+impl ExecutionHook for StepLimit {
+    fn on_brillig_step(&mut self, _memory: &[MemoryValue<FieldElement>]) -> Result<(), Error> {
+        self.remaining = self
+            .remaining
+            .checked_sub(1)
+            .ok_or(Error::StepLimitExceeded)?;
+
+        Ok(())
+    }
+}
+
+/// Executes a program step by step, calling the hook between solver steps.
+///
+/// This mirrors `nargo::ops::execute_program`, but steps through Brillig calls one instruction at
+/// a time so the hook can observe the execution loop.
+///
+/// ## Returns
+///
+/// Returns the solved witness stack, or nargo's error if the program fails to execute, built as
+/// `nargo::ops::execute_program` builds it so that it can be diagnosed the same way.
+///
+/// ## Errors
+///
+/// Returns the hook's error if it aborts execution.
+pub(crate) fn execute_program_with_hook<B, E, H>(
+    program: &Program<FieldElement>,
+    initial_witness: WitnessMap<FieldElement>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+    hook: &mut H,
+) -> Result<Result<WitnessStack<FieldElement>, NargoError<FieldElement>>, Error>
+where
+    B: BlackBoxFunctionSolver<FieldElement>,
+    E: ForeignCallExecutor<FieldElement>,
+    H: ExecutionHook,
+{
+    let mut executor = SteppingExecutor {
+        program,
+        blackbox_solver,
+        foreign_call_executor,
+        hook,
+        witness_stack: WitnessStack::default(),
+        call_stack: Vec::new(),
+    };
+
+    match executor.execute_circuit(0, initial_witness) {
+        Ok(main_witness) => {
+            executor.witness_stack.push(0, main_witness);
+
+            Ok(Ok(executor.witness_stack))
+        }
+        Err(Halt::Error(err)) => Err(err),
+        Err(Halt::Execution(err)) => Ok(Err(err)),
+    }
+}
+
+/// Why stepping through a program stopped before it was solved.
+enum Halt {
+    /// The hook aborted execution, or the solver reached an inconsistent state.
+    Error(Error),
+    /// The program failed to execute.
+    Execution(NargoError<FieldElement>),
+}
+
+impl From<Error> for Halt {
+    fn from(err: Error) -> Self {
+        Self::Error(err)
+    }
+}
+
+struct SteppingExecutor<'a, B, E, H> {
+    program: &'a Program<FieldElement>,
+    blackbox_solver: &'a B,
+    foreign_call_executor: &'a mut E,
+    hook: &'a mut H,
+    witness_stack: WitnessStack<FieldElement>,
+    /// The locations of the ACIR calls leading to the circuit being executed, outermost first.
+    call_stack: Vec<ResolvedOpcodeLocation>,
+}
+
+impl<B, E, H> SteppingExecutor<'_, B, E, H>
+where
+    B: BlackBoxFunctionSolver<FieldElement>,
+    E: ForeignCallExecutor<FieldElement>,
+    H: ExecutionHook,
+{
+    fn execute_circuit(
+        &mut self,
+        function_index: usize,
+        initial_witness: WitnessMap<FieldElement>,
+    ) -> Result<WitnessMap<FieldElement>, Halt> {
+        let program = self.program;
+        let circuit = &program.functions[function_index];
+
+        let mut acvm = ACVM::new(
+            self.blackbox_solver,
+            &circuit.opcodes,
+            initial_witness,
+            &program.unconstrained_functions,
+            &circuit.assert_messages,
+        );
+
+        let mut status = acvm.get_status().clone();
+
+        loop {
+            match status {
+                ACVMStatus::Solved => break,
+                ACVMStatus::InProgress => {
+                    status = match acvm.step_into_brillig() {
+                        StepResult::Status(status) => {
+                            self.hook.on_acir_step()?;
+                            status
+                        }
+                        StepResult::IntoBrillig(mut solver) => loop {
+                            let brillig_status = solver
+                                .step()
+                                .map_err(|err| self.execution_error(function_index, err))?;
+
+                            match brillig_status {
+                                BrilligSolverStatus::InProgress => {}
+                                BrilligSolverStatus::Finished => {
+                                    self.hook.on_brillig_step(solver.get_memory())?;
+                                    break acvm.finish_brillig_with_solver(solver);
+                                }
+                                BrilligSolverStatus::ForeignCallWait(foreign_call) => {
+                                    let result = self
+                                        .foreign_call_executor
+                                        .execute(&foreign_call)
+                                        .map_err(NargoError::ForeignCallError)
+                                        .map_err(Halt::Execution)?;
+                                    solver.resolve_pending_foreign_call(result);
+                                }
+                            }
+
+                            self.hook.on_brillig_step(solver.get_memory())?;
+                        },
+                    };
+                }
+                ACVMStatus::Failure(err) => {
+                    return Err(self.execution_error(function_index, err));
+                }
+                ACVMStatus::RequiresForeignCall(foreign_call) => {
+                    let result = self
+                        .foreign_call_executor
+                        .execute(&foreign_call)
+                        .map_err(NargoError::ForeignCallError)
+                        .map_err(Halt::Execution)?;
+                    acvm.resolve_pending_foreign_call(result);
+                    status = ACVMStatus::InProgress;
+                }
+                ACVMStatus::RequiresAcirCall(call_info) => {
+                    let callee_index = call_info.id.as_usize();
+
+                    self.call_stack.push(ResolvedOpcodeLocation {
+                        acir_function_index: function_index,
+                        opcode_location: OpcodeLocation::Acir(acvm.instruction_pointer()),
+                    });
+
+                    let callee_witness =
+                        self.execute_circuit(callee_index, call_info.initial_witness)?;
+
+                    self.call_stack.pop();
+
+                    let return_values = program.functions[callee_index]
+                        .return_values
+                        .indices()
+                        .into_iter()
+                        .map(|index| {
+                            callee_witness.get_index(index).copied().ok_or_else(|| {
+                                Error::Nargo(format!("missing assignment for witness {index}"))
+                            })
+                        })
+                        .collect::<Result<Vec<_>, Error>>()?;
+
+                    acvm.resolve_pending_acir_call(return_values);
+                    self.witness_stack.push(call_info.id.0, callee_witness);
+                    status = ACVMStatus::InProgress;
+                }
+            }
+        }
+
+        let witness = acvm.finalize();
+        self.hook.on_circuit_solved(&witness)?;
+
+        Ok(witness)
+    }
+
+    /// Builds nargo's error for a failed opcode, with the call stack leading to it and the
+    /// assertion payload, if any.
+    fn execution_error(
+        &self,
+        function_index: usize,
+        err: OpcodeResolutionError<FieldElement>,
+    ) -> Halt {
+        let resolve = |opcode_location: OpcodeLocation| ResolvedOpcodeLocation {
+            acir_function_index: function_index,
+            opcode_location,
+        };
+
+        let failure_stack = match &err {
+            OpcodeResolutionError::UnsatisfiedConstrain {
+                opcode_location: ErrorLocation::Resolved(opcode_location),
+                ..
+            }
+            | OpcodeResolutionError::IndexOutOfBounds {
+                opcode_location: ErrorLocation::Resolved(opcode_location),
+                ..
+            }
+            | OpcodeResolutionError::InvalidInputBitSize {
+                opcode_location: ErrorLocation::Resolved(opcode_location),
+                ..
+            } => Some(vec![resolve(*opcode_location)]),
+            OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
+                Some(call_stack.iter().copied().map(resolve).collect())
+            }
+            _ => None,
+        };
+
+        let call_stack = failure_stack.map(|failure_stack| {
+            self.call_stack
+                .iter()
+                .cloned()
+                .chain(failure_stack)
+                .collect::<Vec<_>>()
+        });
+
+        let payload = match &err {
+            OpcodeResolutionError::BrilligFunctionFailed { payload, .. }
+            | OpcodeResolutionError::UnsatisfiedConstrain { payload, .. } => payload.clone(),
+            _ => None,
+        };
+
+        let brillig_function_id = match &err {
+            OpcodeResolutionError::BrilligFunctionFailed { function_id, .. } => Some(*function_id),
+            _ => None,
+        };
+
+        let err = match (payload, call_stack) {
+            (Some(payload), Some(call_stack)) => {
+                ExecutionError::AssertionFailed(payload, call_stack, brillig_function_id)
+            }
+            (_, call_stack) => ExecutionError::SolvingError(err, call_stack),
+        };
+
+        Halt::Execution(NargoError::ExecutionError(err))
+    }
+}
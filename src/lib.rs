@@ -45,15 +45,83 @@
 //! assert_eq!(result, (x + y).to_noir());
 //! ```
 //!
+//! ## Tracing
+//!
+//! With the `tracing` feature, the runner emits `debug` level spans around loading artifacts,
+//! encoding inputs, solving the witness and decoding outputs, nested under a span for each run
+//! recording the function name. Span timings are reported by the subscriber. Without the feature,
+//! no instrumentation is compiled in.
+//!
 //! ## Re Exports
 //!
+//! - [`Abi`]: (`noirc_abi`) Describes a Noir program's parameters and return type.
+//! - [`AbiType`]: (`noirc_abi`) Describes the type of a Noir program's parameter or return value.
+//! - [`BlackBoxFunctionSolver`]: (`acvm`) Solves the black box function calls, such as hashes, made
+//!   by a Noir program.
+//! - [`Bn254BlackBoxSolver`]: (`bn254_blackbox_solver`) The black box solver for the BN254 curve,
+//!   used by default.
 //! - [`FieldElement`]: (`acvm`) Represents a field element in the BN254 curve.
+//! - [`ForeignCallExecutor`]: (`nargo`) Resolves the foreign calls, such as oracles, made by a
+//!   Noir program.
 //! - [`InputValue`]: (`noirc_abi`) Represents a value that can be passed as an input to a Noir program.
+//! - [`ProgramArtifact`]: (`noirc_artifacts`) A Noir program as exported by `nargo export`.
+//! - [`WitnessMap`]: (`acvm`) Maps witness indices to their assigned field elements.
+//! - [`WitnessStack`]: (`acvm`) Holds the solved witness map of each circuit executed by a program.
 
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private;
 mod abi;
+mod builder;
+mod cache;
+#[cfg(feature = "compile")]
+mod compile;
+#[cfg(feature = "conformance")]
+mod conformance;
+mod de;
+mod encoder;
 mod error;
+mod execution;
+mod metrics;
+mod number;
+mod option;
+mod outcome;
+mod parse;
+#[cfg(feature = "protobuf")]
+mod protobuf;
+mod prove;
+mod report;
 mod runner;
+mod ser;
+mod session;
+mod signature;
+mod signed;
+mod validation;
 
-pub use abi::{FieldElement, InputValue, ToNoir};
+pub use abi::{
+    Abi, AbiType, BITS_PER_FIELD, FieldElement, FromNoir, InputValue, NoirEq, ProgramArtifact,
+    ToNoir, WitnessMap, WitnessStack, bits_to_fields, bytes_to_field_be, bytes_to_field_le,
+    canonical_input_bytes, duration_as_millis_field, duration_as_secs_field, field_from_dec_str,
+    field_from_hex, field_to_i64, field_to_u64, field_to_u128, flatten_result, input_value_to_json,
+    merkle_path_to_fields, struct_value,
+};
+pub use builder::NoirRunnerBuilder;
+#[cfg(feature = "conformance")]
+pub use conformance::{ConformanceReport, compare_with_nargo};
+pub use de::from_input_value;
+pub use encoder::{InputEncoder, InputTransform};
 pub use error::Error;
+pub use execution::{
+    BlackBoxFunctionSolver, Bn254BlackBoxSolver, ForeignCallExecutor, ResourceStats,
+};
+pub use metrics::CircuitMetrics;
+#[cfg(feature = "derive")]
+pub use noir_runner_derive::Noir;
+pub use number::{NumberConversion, NumberStrategy};
+pub use outcome::RunOutcome;
+#[cfg(feature = "protobuf")]
+pub use protobuf::{input_value_from_protobuf, input_value_to_protobuf};
+pub use prove::Proof;
+pub use report::ValidationReport;
 pub use runner::NoirRunner;
+pub use session::RunSession;
@@ -0,0 +1,47 @@
+use acvm::FieldElement;
+use acvm::acir::circuit::Program;
+
+/// Circuit Metrics
+///
+/// The static size of a compiled program, refer to [`NoirRunner::metrics`].
+///
+/// [`NoirRunner::metrics`]: crate::NoirRunner::metrics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CircuitMetrics {
+    /// The number of ACIR opcodes, across every ACIR function in the program.
+    pub acir_opcodes: usize,
+    /// The number of Brillig opcodes, across every unconstrained function in the program.
+    pub brillig_opcodes: usize,
+    /// The number of public input witnesses of the main circuit, excluding its return values.
+    pub public_inputs: usize,
+    /// The number of private input witnesses of the main circuit.
+    pub private_inputs: usize,
+    /// The number of witnesses, across every ACIR function in the program.
+    pub witness_count: usize,
+}
+
+impl CircuitMetrics {
+    pub(crate) fn new(program: &Program<FieldElement>) -> Self {
+        let main = program.functions.first();
+
+        Self {
+            acir_opcodes: program
+                .functions
+                .iter()
+                .map(|circuit| circuit.opcodes.len())
+                .sum(),
+            brillig_opcodes: program
+                .unconstrained_functions
+                .iter()
+                .map(|function| function.bytecode.len())
+                .sum(),
+            public_inputs: main.map_or(0, |circuit| circuit.public_parameters.0.len()),
+            private_inputs: main.map_or(0, |circuit| circuit.private_parameters.len()),
+            witness_count: program
+                .functions
+                .iter()
+                .map(|circuit| circuit.current_witness_index as usize + 1)
+                .sum(),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use crate::Error;
+use crate::ser::SerializeError;
+
+use noirc_abi::input_parser::InputValue;
+use serde::ser::Error as _;
+use serde_json::Number;
+
+/// A custom number conversion, refer to [`NumberStrategy::Custom`].
+pub type NumberConversion = Arc<dyn Fn(&Number) -> Result<InputValue, Error> + Send + Sync>;
+
+/// Number Strategy
+///
+/// Controls how numbers are converted into field elements when a value is serialized into an
+/// [`InputValue`]. Integers are converted as is by every built-in strategy, negative integers
+/// wrap around the field modulus. The strategies differ in how they treat floats, Noir has no
+/// float type so fractional values are rejected by default rather than silently truncated.
+///
+/// NaN and infinite floats always return [`Error::NonFiniteFloat`], as they are not numbers.
+#[derive(Clone, Default)]
+pub enum NumberStrategy {
+    /// Floats are truncated towards zero and saturated to a `u64`, so `1.9` converts to `1` and
+    /// `-1.0` converts to `0`.
+    TruncateToU64,
+    /// Floats must hold an exact integer within the range of an `i64` or a `u64`, so `2.0`
+    /// converts to `2` while `1.5` returns an error. Negative floats wrap around the field modulus
+    /// as negative integers do, so `-1.0` converts as `-1`. This is the default.
+    #[default]
+    ExactInteger,
+    /// Floats always return an error.
+    RejectFloat,
+    /// Numbers are converted by the given function, including integers.
+    Custom(NumberConversion),
+}
+
+impl NumberStrategy {
+    pub(crate) fn convert(&self, number: &Number) -> Result<InputValue, SerializeError> {
+        if let Self::Custom(conversion) = self {
+            return conversion(number).map_err(SerializeError::Strategy);
+        }
+
+        if let Some(value) = number.as_u64() {
+            return Ok(InputValue::Field(value.into()));
+        }
+
+        if let Some(value) = number.as_i64() {
+            return Ok(InputValue::Field(i128::from(value).into()));
+        }
+
+        let value = number.as_f64().unwrap_or(f64::NAN);
+
+        // -2^63 and 2^64 are exactly representable as floats, unlike u64::MAX which rounds up
+        let is_exact = value.fract() == 0.0
+            && (-9_223_372_036_854_775_808.0..18_446_744_073_709_551_616.0).contains(&value);
+
+        match self {
+            Self::ExactInteger if is_exact => Ok(InputValue::Field((value as i128).into())),
+            Self::ExactInteger => Err(SerializeError::custom(format!(
+                "{value} is not an exact integer"
+            ))),
+            Self::RejectFloat => Err(SerializeError::custom(format!("{value} is a float"))),
+            _ => Ok(InputValue::Field((value as u64).into())),
+        }
+    }
+}
+
+impl Debug for NumberStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TruncateToU64 => f.write_str("TruncateToU64"),
+            Self::ExactInteger => f.write_str("ExactInteger"),
+            Self::RejectFloat => f.write_str("RejectFloat"),
+            Self::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ser::to_input_value_with;
+
+    fn field(value: u64) -> InputValue {
+        InputValue::Field(value.into())
+    }
+
+    #[test]
+    fn test_truncate_to_u64() {
+        let strategy = NumberStrategy::TruncateToU64;
+
+        assert_eq!(to_input_value_with(&7u32, &strategy).unwrap(), field(7));
+        assert_eq!(to_input_value_with(&1.9, &strategy).unwrap(), field(1));
+        assert_eq!(to_input_value_with(&-1.0, &strategy).unwrap(), field(0));
+    }
+
+    #[test]
+    fn test_exact_integer() {
+        let strategy = NumberStrategy::ExactInteger;
+
+        assert_eq!(to_input_value_with(&7u32, &strategy).unwrap(), field(7));
+        assert_eq!(to_input_value_with(&2.0, &strategy).unwrap(), field(2));
+
+        assert_eq!(
+            to_input_value_with(&-1.0, &strategy).unwrap(),
+            to_input_value_with(&-1i64, &strategy).unwrap()
+        );
+
+        assert!(matches!(
+            to_input_value_with(&1.5, &strategy),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(matches!(
+            to_input_value_with(&1e20, &strategy),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(matches!(
+            to_input_value_with(&-1e20, &strategy),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn test_default_rejects_fractional() {
+        let strategy = NumberStrategy::default();
+
+        assert!(matches!(
+            to_input_value_with(&2.9, &strategy),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn test_reject_float() {
+        let strategy = NumberStrategy::RejectFloat;
+
+        assert_eq!(to_input_value_with(&7u32, &strategy).unwrap(), field(7));
+
+        assert!(matches!(
+            to_input_value_with(&2.0, &strategy),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn test_custom() {
+        // fixed point with three decimal places
+        let strategy = NumberStrategy::Custom(Arc::new(|number: &Number| {
+            let value = number.as_f64().unwrap_or_default();
+
+            Ok(InputValue::Field(((value * 1000.0).round() as u64).into()))
+        }));
+
+        assert_eq!(to_input_value_with(&1.25, &strategy).unwrap(), field(1250));
+        assert_eq!(
+            to_input_value_with(&vec![2u32], &strategy).unwrap(),
+            InputValue::Vec(vec![field(2000)])
+        );
+    }
+
+    #[test]
+    fn test_non_finite_float() {
+        let strategy = NumberStrategy::RejectFloat;
+
+        let result = to_input_value_with(&f64::NAN, &strategy);
+
+        assert!(matches!(result, Err(Error::NonFiniteFloat(_))));
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+
+use noirc_abi::input_parser::InputValue;
+use noirc_abi::{Abi, AbiType};
+
+/// The struct field holding the presence flag of an optional value.
+pub(crate) const PRESENCE_FIELD: &str = "has_value";
+
+/// The struct field holding the value of an optional value.
+pub(crate) const VALUE_FIELD: &str = "value";
+
+/// Fills the value of every absent optional input with the zero value of its ABI type.
+///
+/// `None` converts to a struct holding only a `has_value` flag of `0`, as its value has no shape
+/// until it is matched against the ABI. The circuit still expects a `value` field, so it is
+/// filled here, at any nesting depth.
+pub(crate) fn fill_absent_options(abi: &Abi, input_map: &mut BTreeMap<String, InputValue>) {
+    for parameter in &abi.parameters {
+        if let Some(value) = input_map.get_mut(&parameter.name) {
+            fill_absent_option(&parameter.typ, value);
+        }
+    }
+}
+
+fn fill_absent_option(typ: &AbiType, value: &mut InputValue) {
+    match (typ, value) {
+        (AbiType::Array { typ, .. }, InputValue::Vec(values)) => {
+            values
+                .iter_mut()
+                .for_each(|value| fill_absent_option(typ, value));
+        }
+        (AbiType::Tuple { fields }, InputValue::Vec(values)) => {
+            fields
+                .iter()
+                .zip(values)
+                .for_each(|(typ, value)| fill_absent_option(typ, value));
+        }
+        (AbiType::Struct { fields, .. }, InputValue::Struct(values)) => {
+            let is_absent = values.len() == 1 && values.contains_key(PRESENCE_FIELD);
+
+            for (name, typ) in fields {
+                match values.get_mut(name) {
+                    Some(value) => fill_absent_option(typ, value),
+                    None if is_absent && name == VALUE_FIELD => {
+                        values.insert(name.clone(), zero_value(typ));
+                    }
+                    None => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the value of the given ABI type with every field element set to zero.
+fn zero_value(typ: &AbiType) -> InputValue {
+    match typ {
+        AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => {
+            InputValue::Field(0u32.into())
+        }
+        AbiType::Array { length, typ } => {
+            InputValue::Vec((0..*length).map(|_| zero_value(typ)).collect())
+        }
+        AbiType::Tuple { fields } => InputValue::Vec(fields.iter().map(zero_value).collect()),
+        AbiType::Struct { fields, .. } => InputValue::Struct(
+            fields
+                .iter()
+                .map(|(name, typ)| (name.clone(), zero_value(typ)))
+                .collect(),
+        ),
+        AbiType::String { length } => InputValue::String("\0".repeat(*length as usize)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_nested_absent_option() {
+        let optional_u32 = AbiType::Struct {
+            path: "OptionalU32".to_owned(),
+            fields: vec![
+                (PRESENCE_FIELD.to_owned(), AbiType::Boolean),
+                (
+                    VALUE_FIELD.to_owned(),
+                    AbiType::Integer {
+                        sign: noirc_abi::Sign::Unsigned,
+                        width: 32,
+                    },
+                ),
+            ],
+        };
+
+        let typ = AbiType::Struct {
+            path: "Inner".to_owned(),
+            fields: vec![("a".to_owned(), optional_u32)],
+        };
+
+        let mut value = InputValue::Struct(BTreeMap::from([(
+            "a".to_owned(),
+            InputValue::Struct(BTreeMap::from([(
+                PRESENCE_FIELD.to_owned(),
+                InputValue::Field(0u32.into()),
+            )])),
+        )]));
+
+        fill_absent_option(&typ, &mut value);
+
+        let expected = InputValue::Struct(BTreeMap::from([(
+            "a".to_owned(),
+            InputValue::Struct(BTreeMap::from([
+                (PRESENCE_FIELD.to_owned(), InputValue::Field(0u32.into())),
+                (VALUE_FIELD.to_owned(), InputValue::Field(0u32.into())),
+            ])),
+        )]));
+
+        assert_eq!(value, expected);
+    }
+}
@@ -0,0 +1,21 @@
+use noirc_abi::input_parser::InputValue;
+
+/// Run Outcome
+///
+/// The outcome of a run whose inputs were encoded successfully, refer to [`NoirRunner::try_run`].
+///
+/// [`NoirRunner::try_run`]: crate::NoirRunner::try_run
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunOutcome {
+    /// The inputs satisfy the program's constraints, holding the decoded output value, if any.
+    Satisfied(Option<InputValue>),
+    /// The inputs do not satisfy the program's constraints, holding the reason.
+    Unsatisfied(String),
+}
+
+impl RunOutcome {
+    /// Returns `true` if the inputs satisfy the program's constraints.
+    pub fn is_satisfied(&self) -> bool {
+        matches!(self, Self::Satisfied(_))
+    }
+}
@@ -0,0 +1,188 @@
+use crate::Error;
+
+use acvm::{AcirField, FieldElement};
+use noirc_abi::input_parser::InputValue;
+use noirc_abi::{AbiType, Sign};
+
+/// Parses a textual input value against the parameter's ABI type.
+///
+/// - Fields accept decimal (optionally negative) or `0x` prefixed hexadecimal values.
+/// - Integers accept decimal or `0x` prefixed hexadecimal values within the range of their width.
+///   Negative signed integers are encoded in two's complement, matching nargo's ABI encoder.
+/// - Booleans accept `true`, `false`, `1` or `0`.
+/// - Strings accept the text as is.
+///
+/// Arrays, tuples and structs cannot be parsed from text.
+pub(crate) fn parse_input(param: &str, typ: &AbiType, text: &str) -> Result<InputValue, Error> {
+    let invalid = |reason: String| Error::InvalidInput {
+        param: param.to_owned(),
+        reason,
+    };
+
+    let text = text.trim();
+
+    match typ {
+        AbiType::Field => parse_field(text)
+            .map(InputValue::Field)
+            .ok_or_else(|| invalid(format!("`{text}` is not a field element"))),
+        AbiType::Integer { sign, width } => parse_integer(text, sign, *width)
+            .map(InputValue::Field)
+            .ok_or_else(|| invalid(format!("`{text}` is not a {sign:?} {width} bit integer"))),
+        AbiType::Boolean => match text {
+            "true" | "1" => Ok(InputValue::Field(FieldElement::one())),
+            "false" | "0" => Ok(InputValue::Field(FieldElement::zero())),
+            _ => Err(invalid(format!("`{text}` is not a boolean"))),
+        },
+        AbiType::String { .. } => Ok(InputValue::String(text.to_owned())),
+        AbiType::Array { .. } | AbiType::Tuple { .. } | AbiType::Struct { .. } => Err(invalid(
+            "arrays, tuples and structs cannot be parsed from text".to_owned(),
+        )),
+    }
+}
+
+/// Checks that a textual field element is below the field modulus.
+///
+/// Field elements are reduced modulo the BN254 prime when parsed, so a value at or above the
+/// modulus silently wraps around. Only field parameters are checked, integers are range checked
+/// when parsed and other types do not hold field elements.
+pub(crate) fn check_reduced(param: &str, typ: &AbiType, text: &str) -> Result<(), Error> {
+    if *typ != AbiType::Field {
+        return Ok(());
+    }
+
+    let text = text.trim();
+    let magnitude = text.strip_prefix('-').unwrap_or(text);
+
+    let is_reduced = match magnitude.strip_prefix("0x") {
+        Some(hex) => is_below_modulus(hex, 16),
+        None => is_below_modulus(magnitude, 10),
+    };
+
+    if !is_reduced {
+        return Err(Error::InvalidInput {
+            param: param.to_owned(),
+            reason: format!("`{text}` is not reduced modulo the field modulus"),
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the digits, in the given radix, represent a value below the field modulus.
+pub(crate) fn is_below_modulus(digits: &str, radix: u32) -> bool {
+    let digits = digits.trim_start_matches('0').to_ascii_lowercase();
+    let modulus = FieldElement::modulus().to_str_radix(radix);
+
+    (digits.len(), digits.as_str()) < (modulus.len(), modulus.as_str())
+}
+
+fn parse_field(text: &str) -> Option<FieldElement> {
+    match text.strip_prefix('-') {
+        Some(magnitude) => parse_field(magnitude).map(|field| -field),
+        None if text.starts_with("0x") => FieldElement::from_hex(text),
+        None if !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit()) => {
+            FieldElement::try_from_str(text)
+        }
+        None => None,
+    }
+}
+
+fn parse_integer(text: &str, sign: &Sign, width: u32) -> Option<FieldElement> {
+    let (negative, magnitude) = match text.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, text),
+    };
+
+    let magnitude = match magnitude.strip_prefix("0x") {
+        Some(hex) => u128::from_str_radix(hex, 16).ok()?,
+        None => magnitude.parse::<u128>().ok()?,
+    };
+
+    let max = u128::MAX >> (128 - width.min(128));
+
+    match (sign, negative) {
+        (Sign::Unsigned, false) => (magnitude <= max).then(|| magnitude.into()),
+        (Sign::Unsigned, true) => None,
+        (Sign::Signed, false) => (magnitude <= max >> 1).then(|| magnitude.into()),
+        (Sign::Signed, true) => {
+            (magnitude <= (max >> 1) + 1).then(|| (magnitude.wrapping_neg() & max).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(value: u128) -> InputValue {
+        InputValue::Field(value.into())
+    }
+
+    #[test]
+    fn test_parse_integer() {
+        let unsigned = AbiType::Integer {
+            sign: Sign::Unsigned,
+            width: 8,
+        };
+        let signed = AbiType::Integer {
+            sign: Sign::Signed,
+            width: 8,
+        };
+
+        assert_eq!(parse_input("x", &unsigned, "255").unwrap(), field(255));
+        assert_eq!(parse_input("x", &unsigned, "0xff").unwrap(), field(255));
+        assert_eq!(parse_input("x", &signed, "-1").unwrap(), field(255));
+        assert_eq!(parse_input("x", &signed, "-128").unwrap(), field(128));
+
+        assert!(parse_input("x", &unsigned, "256").is_err());
+        assert!(parse_input("x", &unsigned, "-1").is_err());
+        assert!(parse_input("x", &signed, "128").is_err());
+        assert!(parse_input("x", &signed, "-129").is_err());
+    }
+
+    #[test]
+    fn test_parse_field() {
+        assert_eq!(parse_input("x", &AbiType::Field, "42").unwrap(), field(42));
+        assert_eq!(
+            parse_input("x", &AbiType::Field, "0x2a").unwrap(),
+            field(42)
+        );
+        assert_eq!(
+            parse_input("x", &AbiType::Field, "-1").unwrap(),
+            InputValue::Field(-FieldElement::one())
+        );
+
+        assert!(parse_input("x", &AbiType::Field, "forty two").is_err());
+    }
+
+    #[test]
+    fn test_parse_boolean() {
+        assert_eq!(
+            parse_input("x", &AbiType::Boolean, "true").unwrap(),
+            field(1)
+        );
+        assert_eq!(parse_input("x", &AbiType::Boolean, "0").unwrap(), field(0));
+
+        assert!(parse_input("x", &AbiType::Boolean, "yes").is_err());
+    }
+
+    #[test]
+    fn test_check_reduced() {
+        let modulus = FieldElement::modulus();
+        let below = &modulus - 1u32;
+
+        assert!(check_reduced("x", &AbiType::Field, &below.to_string()).is_ok());
+        assert!(check_reduced("x", &AbiType::Field, "-1").is_ok());
+        assert!(check_reduced("x", &AbiType::Field, "0x00ff").is_ok());
+
+        assert!(check_reduced("x", &AbiType::Field, &modulus.to_string()).is_err());
+        assert!(
+            check_reduced(
+                "x",
+                &AbiType::Field,
+                &format!("0x{}", modulus.to_str_radix(16))
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+
+use crate::Error;
+
+use acvm::{AcirField, FieldElement};
+use noirc_abi::input_parser::InputValue;
+use prost_types::value::Kind;
+use prost_types::{ListValue, Struct, Value};
+
+/// Converts an [`InputValue`] into a protobuf [`Value`].
+///
+/// Field elements are rendered as `0x` prefixed hexadecimal strings to preserve their precision,
+/// strings as strings, vectors as lists and structs as structs.
+///
+/// Both field elements and strings become protobuf strings, so the conversion is lossy for a
+/// string that is itself `0x` prefixed hexadecimal, such as `"0x1f"`: it converts back into a
+/// field element rather than a string.
+pub fn input_value_to_protobuf(value: &InputValue) -> Value {
+    let kind = match value {
+        InputValue::Field(field) => Kind::StringValue(format!("0x{}", field.to_hex())),
+        InputValue::String(string) => Kind::StringValue(string.clone()),
+        InputValue::Vec(values) => Kind::ListValue(ListValue {
+            values: values.iter().map(input_value_to_protobuf).collect(),
+        }),
+        InputValue::Struct(fields) => Kind::StructValue(Struct {
+            fields: fields
+                .iter()
+                .map(|(name, value)| (name.clone(), input_value_to_protobuf(value)))
+                .collect(),
+        }),
+    };
+
+    Value { kind: Some(kind) }
+}
+
+/// Converts a protobuf [`Value`] into an [`InputValue`].
+///
+/// This is the inverse of [`input_value_to_protobuf`], except for hexadecimal strings. Strings
+/// that parse as `0x` prefixed hexadecimal become field elements and all other strings remain
+/// strings. Integral numbers and
+/// booleans become field elements, and null becomes the zero field element.
+///
+/// ## Errors
+///
+/// Returns [`Error::InvalidInput`] if the value, or a value nested in it, has no kind, or is a
+/// number that does not convert exactly into a `u128`, such as a negative, fractional,
+/// non-finite or too large number. The `param` is the path to the value, such as `value.a[2]`.
+pub fn input_value_from_protobuf(value: &Value) -> Result<InputValue, Error> {
+    from_protobuf("value", value)
+}
+
+fn from_protobuf(path: &str, value: &Value) -> Result<InputValue, Error> {
+    let invalid = |reason: String| Error::InvalidInput {
+        param: path.to_owned(),
+        reason,
+    };
+
+    let kind = value
+        .kind
+        .as_ref()
+        .ok_or_else(|| invalid("protobuf value has no kind".to_owned()))?;
+
+    let value = match kind {
+        Kind::NullValue(_) => InputValue::Field(FieldElement::zero()),
+        Kind::BoolValue(b) => InputValue::Field((*b).into()),
+        Kind::NumberValue(n) => InputValue::Field(
+            number_to_u128(*n)
+                .ok_or_else(|| invalid(format!("{n} is not an integer in the range of a u128")))?
+                .into(),
+        ),
+        Kind::StringValue(string) => match string.strip_prefix("0x") {
+            Some(_) => FieldElement::from_hex(string)
+                .map(InputValue::Field)
+                .unwrap_or_else(|| InputValue::String(string.clone())),
+            None => InputValue::String(string.clone()),
+        },
+        Kind::ListValue(list) => InputValue::Vec(
+            list.values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| from_protobuf(&format!("{path}[{index}]"), value))
+                .collect::<Result<_, _>>()?,
+        ),
+        Kind::StructValue(fields) => InputValue::Struct(
+            fields
+                .fields
+                .iter()
+                .map(|(name, value)| {
+                    Ok((
+                        name.clone(),
+                        from_protobuf(&format!("{path}.{name}"), value)?,
+                    ))
+                })
+                .collect::<Result<BTreeMap<_, _>, Error>>()?,
+        ),
+    };
+
+    Ok(value)
+}
+
+/// Converts a number into a `u128` if it round-trips exactly.
+fn number_to_u128(n: f64) -> Option<u128> {
+    // casting saturates at `u128::MAX`, which converts back to exactly 2^128
+    if !(0.0..2f64.powi(128)).contains(&n) {
+        return None;
+    }
+
+    let integer = n as u128;
+
+    (integer as f64 == n).then_some(integer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_round_trip() {
+        let value = InputValue::Field(-FieldElement::one());
+
+        let protobuf = input_value_to_protobuf(&value);
+
+        assert_eq!(input_value_from_protobuf(&protobuf).unwrap(), value);
+    }
+
+    #[test]
+    fn test_array_round_trip() {
+        let value = InputValue::Vec(vec![
+            InputValue::Field(1u32.into()),
+            InputValue::Field(2u32.into()),
+        ]);
+
+        let protobuf = input_value_to_protobuf(&value);
+
+        assert_eq!(input_value_from_protobuf(&protobuf).unwrap(), value);
+    }
+
+    #[test]
+    fn test_struct_round_trip() {
+        let value = InputValue::Struct(BTreeMap::from([
+            ("a".to_string(), InputValue::Field(1u32.into())),
+            ("b".to_string(), InputValue::String("hello".to_string())),
+            (
+                "c".to_string(),
+                InputValue::Vec(vec![InputValue::Field(3u32.into())]),
+            ),
+        ]));
+
+        let protobuf = input_value_to_protobuf(&value);
+
+        assert_eq!(input_value_from_protobuf(&protobuf).unwrap(), value);
+    }
+
+    #[test]
+    fn test_hex_string_is_lossy() {
+        let value = InputValue::String("0x1f".to_string());
+
+        let protobuf = input_value_to_protobuf(&value);
+
+        assert_eq!(
+            input_value_from_protobuf(&protobuf).unwrap(),
+            InputValue::Field(31u32.into())
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        let number = |n| Value {
+            kind: Some(Kind::NumberValue(n)),
+        };
+
+        assert_eq!(
+            input_value_from_protobuf(&number(42.0)).unwrap(),
+            InputValue::Field(42u32.into())
+        );
+
+        for n in [-1.0, 0.5, f64::NAN, f64::INFINITY, 2f64.powi(128), 1e40] {
+            assert!(matches!(
+                input_value_from_protobuf(&number(n)),
+                Err(Error::InvalidInput { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_error_path() {
+        let value = Value {
+            kind: Some(Kind::ListValue(ListValue {
+                values: vec![Value { kind: None }],
+            })),
+        };
+
+        assert!(matches!(
+            input_value_from_protobuf(&value),
+            Err(Error::InvalidInput { param, .. }) if param == "value[0]"
+        ));
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::BTreeSet;
+use std::ffi::OsStr;
+use std::fs::DirBuilder;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::Error;
+
+use acvm::acir::native_types::{Witness, WitnessStack};
+use acvm::{AcirField, FieldElement};
+use noirc_artifacts::program::ProgramArtifact;
+use noirc_driver::CompiledProgram;
+
+/// Proof
+///
+/// An UltraHonk proof generated by the Barretenberg prover, refer to [`NoirRunner::prove`].
+///
+/// [`NoirRunner::prove`]: crate::NoirRunner::prove
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+    /// The proof, as written by `bb prove`.
+    pub proof: Vec<u8>,
+    /// The public inputs of the main circuit, including its return values, in witness order.
+    pub public_inputs: Vec<FieldElement>,
+}
+
+/// Returns the public witnesses of the main circuit, its public parameters and return values, in
+/// witness order.
+pub(crate) fn public_witnesses(program: &CompiledProgram) -> BTreeSet<Witness> {
+    program
+        .program
+        .functions
+        .first()
+        .map(|circuit| {
+            circuit
+                .public_parameters
+                .0
+                .union(&circuit.return_values.0)
+                .copied()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads the values of the public witnesses of the main circuit from a solved witness stack.
+pub(crate) fn public_inputs(
+    program: &CompiledProgram,
+    solved_witness_stack: &WitnessStack<FieldElement>,
+) -> Result<Vec<FieldElement>, Error> {
+    let Some(main) = solved_witness_stack.peek() else {
+        return Err(Error::Nargo("empty witness stack".to_owned()));
+    };
+
+    public_witnesses(program)
+        .into_iter()
+        .map(|witness| {
+            main.witness.get(&witness).copied().ok_or_else(|| {
+                Error::Nargo(format!(
+                    "public witness {} is not solved",
+                    witness.witness_index()
+                ))
+            })
+        })
+        .collect()
+}
+
+/// A scratch directory holding the files exchanged with the prover, removed once dropped.
+pub(crate) struct ProverDir(PathBuf);
+
+impl ProverDir {
+    /// Creates a new scratch directory in the system's temporary directory.
+    ///
+    /// The directory is created exclusively, an existing directory or symlink at its path is never
+    /// reused, so files left behind by another process cannot be read back. On Unix it is only
+    /// accessible by the current user.
+    pub(crate) fn new() -> Result<Self, Error> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let dir = std::env::temp_dir().join(format!(
+            "noir-runner-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let mut builder = DirBuilder::new();
+
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+        // `create` fails with `AlreadyExists` rather than reusing the path
+        builder.create(&dir).map_err(Error::Io)?;
+
+        Ok(Self(dir))
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Writes the program as a JSON artifact, the bytecode format read by the prover.
+    pub(crate) fn write_program(&self, program: &CompiledProgram) -> Result<PathBuf, Error> {
+        let artifact = ProgramArtifact::from(program.clone());
+        let bytes = serde_json::to_vec(&artifact).map_err(Error::Serde)?;
+
+        self.write("program.json", &bytes)
+    }
+
+    /// Writes field elements as consecutive 32 byte big-endian integers.
+    pub(crate) fn write_fields(
+        &self,
+        name: &str,
+        fields: &[FieldElement],
+    ) -> Result<PathBuf, Error> {
+        let bytes = fields
+            .iter()
+            .flat_map(|field| field.to_be_bytes())
+            .collect::<Vec<_>>();
+
+        self.write(name, &bytes)
+    }
+
+    pub(crate) fn write(&self, name: &str, bytes: &[u8]) -> Result<PathBuf, Error> {
+        let path = self.0.join(name);
+
+        std::fs::write(&path, bytes).map_err(Error::Io)?;
+
+        Ok(path)
+    }
+
+    pub(crate) fn read(&self, name: &str) -> Result<Vec<u8>, Error> {
+        std::fs::read(self.0.join(name)).map_err(Error::Io)
+    }
+}
+
+impl Drop for ProverDir {
+    fn drop(&mut self) {
+        // a leftover scratch directory is harmless, so failing to remove it is ignored
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A run of the prover that exited unsuccessfully.
+pub(crate) struct ProverFailure {
+    /// The exit code, `None` if the prover was terminated by a signal.
+    code: Option<i32>,
+    stdout: String,
+    stderr: String,
+}
+
+impl ProverFailure {
+    /// Returns `true` if the prover checked the proof and rejected it, as opposed to failing to
+    /// check it, such as on a malformed proof or verification key.
+    ///
+    /// `bb verify` exits with `1` both on a rejected proof and on an error, but only logs
+    /// `verified: 0` once the proof was actually checked.
+    pub(crate) fn is_rejection(&self) -> bool {
+        self.code == Some(1)
+            && [&self.stdout, &self.stderr]
+                .iter()
+                .any(|output| output.contains("verified: 0"))
+    }
+
+    pub(crate) fn into_error(self) -> Error {
+        let reason = match self.code {
+            Some(code) => format!("exited with {code}: {}", self.stderr),
+            None => format!("terminated by a signal: {}", self.stderr),
+        };
+
+        Error::Prover(reason)
+    }
+}
+
+/// Runs the prover binary with the given arguments.
+///
+/// ## Returns
+///
+/// Returns `Ok(())` if the prover exited successfully, otherwise how it failed.
+///
+/// ## Errors
+///
+/// Returns [`Error::Prover`] if the prover cannot be started.
+pub(crate) fn run_prover<I, S>(binary: &Path, args: I) -> Result<Result<(), ProverFailure>, Error>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = Command::new(binary)
+        .args(args)
+        .output()
+        .map_err(|err| Error::Prover(format!("failed to run `{}`: {err}", binary.display())))?;
+
+    if !output.status.success() {
+        return Ok(Err(ProverFailure {
+            code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).trim().to_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        }));
+    }
+
+    Ok(Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prover_dir_is_exclusive() {
+        let dir = ProverDir::new().unwrap();
+        let other = ProverDir::new().unwrap();
+
+        assert_ne!(dir.path(), other.path());
+        assert!(dir.path().is_dir());
+
+        let path = dir.path().to_owned();
+        drop(dir);
+
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_is_rejection() {
+        let failure = |code, stderr: &str| ProverFailure {
+            code,
+            stdout: String::new(),
+            stderr: stderr.to_owned(),
+        };
+
+        assert!(failure(Some(1), "verified: 0").is_rejection());
+        assert!(!failure(Some(1), "Unable to open file: proof").is_rejection());
+        assert!(!failure(None, "verified: 0").is_rejection());
+    }
+}
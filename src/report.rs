@@ -0,0 +1,34 @@
+use std::collections::BTreeMap;
+
+use crate::Error;
+
+/// Validation Report
+///
+/// The outcome of loading and checking every exported function, keyed by function name.
+#[derive(Debug)]
+pub struct ValidationReport {
+    /// The result of validating each function.
+    pub results: BTreeMap<String, Result<(), Error>>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if every function was validated successfully.
+    pub fn is_ok(&self) -> bool {
+        self.results.values().all(Result::is_ok)
+    }
+
+    /// Returns the functions that were validated successfully.
+    pub fn successes(&self) -> impl Iterator<Item = &str> {
+        self.results
+            .iter()
+            .filter(|(_, result)| result.is_ok())
+            .map(|(fn_name, _)| fn_name.as_str())
+    }
+
+    /// Returns the functions that failed validation along with the reason.
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &Error)> {
+        self.results.iter().filter_map(|(fn_name, result)| {
+            result.as_ref().err().map(|err| (fn_name.as_str(), err))
+        })
+    }
+}
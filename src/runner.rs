@@ -1,27 +1,88 @@
-use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
-use std::{collections::BTreeMap, fmt::Debug};
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsStr;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 
-use crate::Error;
+use crate::builder::resolve_export_directory;
+use crate::cache::{LruCache, ResultCache};
+use crate::encoder::{InputEncoders, InputTransforms};
+use crate::execution::{
+    Deadline, ExecutionHook, ResourceStats, StepLimit, execute_program_with_hook,
+};
+use crate::metrics::CircuitMetrics;
+use crate::option::fill_absent_options;
+use crate::parse::{check_reduced, parse_input};
+use crate::prove::{Proof, ProverDir, ProverFailure, public_inputs, run_prover};
+use crate::ser::to_input_value_with;
+use crate::signature::render_signature;
+use crate::signed::{decode_signed, decode_signed_inputs, encode_signed_inputs};
+use crate::validation::{validate_input_types, variant_name};
+use crate::{
+    Error, FromNoir, NoirRunnerBuilder, NumberStrategy, RunOutcome, RunSession, ValidationReport,
+};
 
-use acvm::FieldElement;
+use acvm::acir::circuit::Opcode;
+use acvm::acir::native_types::{Witness, WitnessMap, WitnessStack};
+use acvm::{BlackBoxFunctionSolver, FieldElement};
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
-use nargo::foreign_calls::DefaultForeignCallBuilder;
-use nargo::{NargoError, errors::try_to_diagnose_runtime_error, ops::execute_program};
-use nargo_toml::{PackageSelection, get_package_manifest, resolve_workspace_from_toml};
+use nargo::errors::{ExecutionError, try_to_diagnose_runtime_error};
+use nargo::foreign_calls::{DefaultForeignCallBuilder, ForeignCallExecutor, PrintOutput};
+use nargo::{NargoError, ops::execute_program};
 use noirc_abi::input_parser::InputValue;
+use noirc_abi::{Abi, AbiType};
 use noirc_artifacts::{debug::DebugArtifact, program::ProgramArtifact};
 use noirc_driver::{CompiledProgram, NOIR_ARTIFACT_VERSION_STRING};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
 /// Noir Program Runner
 ///
 /// This struct is used to run Noir programs, it encapsulates the program root directory and the
 /// nargo export directory derived from the `Nargo.toml` manifest.
-#[derive(Debug, Clone)]
+///
+/// Deserialized programs are cached by function name, refer to
+/// [`NoirRunnerBuilder::max_cached_artifacts`] to bound the cache size. Run results can be
+/// memoized as well, refer to [`NoirRunnerBuilder::memoize_results`].
+///
+/// ## Concurrency
+///
+/// The runner is `Send + Sync`, so it can be shared across threads behind an `Arc` and run
+/// concurrently through `&self`. The artifact and result caches are guarded by mutexes that are
+/// only held while looking up or inserting an entry, never while a program executes, so
+/// concurrent runs do not serialize on each other. Two threads loading the same uncached function
+/// may both read its artifact, the later insert replaces the earlier. Registered encoders,
+/// transforms and number conversions are required to be `Send + Sync` for the same reason.
+#[derive(Debug)]
 pub struct NoirRunner {
-    program_dir: PathBuf,
-    export_directory: PathBuf,
+    pub(crate) program_dir: PathBuf,
+    pub(crate) export_directory: PathBuf,
+    pub(crate) artifacts: Artifacts,
+    pub(crate) cache: Mutex<LruCache<String, Arc<CompiledProgram>>>,
+    pub(crate) encoders: InputEncoders,
+    pub(crate) results: Option<ResultCache>,
+    pub(crate) catch_panics: bool,
+    pub(crate) reduced_fields: bool,
+    pub(crate) latest_version: bool,
+    pub(crate) transforms: InputTransforms,
+    pub(crate) numbers: NumberStrategy,
+    pub(crate) max_brillig_steps: Option<usize>,
+    pub(crate) report_diagnostics: bool,
+    pub(crate) prover_binary: PathBuf,
+    pub(crate) from_manifest: bool,
+}
+
+/// Where the runner reads program artifacts from.
+#[derive(Debug, Clone)]
+pub(crate) enum Artifacts {
+    /// Artifacts are read from the export directory.
+    ExportDirectory,
+    /// Artifacts are embedded in the binary or held in memory, keyed by function name.
+    Embedded(BTreeMap<String, Cow<'static, [u8]>>),
 }
 
 impl NoirRunner {
@@ -44,19 +105,130 @@ impl NoirRunner {
     /// let program_dir = std::path::PathBuf::from("my_noir_project");
     /// let runner = NoirRunner::try_new(program_dir).unwrap();
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(program_dir = %program_dir.display()))
+    )]
     pub fn try_new(program_dir: PathBuf) -> Result<Self, Error> {
-        let export_directory = resolve_workspace_from_toml(
-            &get_package_manifest(&program_dir).map_err(Error::NargoManifest)?,
-            PackageSelection::All,
-            Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
-        )
-        .map_err(Error::NargoManifest)?
-        .export_directory_path();
+        Self::builder(program_dir).build()
+    }
 
-        Ok(Self {
-            program_dir,
-            export_directory,
-        })
+    /// Creates a [`NoirRunnerBuilder`] for the given program directory.
+    ///
+    /// ## Arguments
+    ///
+    /// - `program_dir`: The root directory of the Noir program.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use noir_runner::NoirRunner;
+    ///
+    /// let program_dir = std::path::PathBuf::from("my_noir_project");
+    /// let runner = NoirRunner::builder(program_dir).max_cached_artifacts(16).build().unwrap();
+    /// ```
+    pub fn builder(program_dir: PathBuf) -> NoirRunnerBuilder {
+        NoirRunnerBuilder::new(program_dir)
+    }
+
+    /// Constructs a [`NoirRunner`] from an export directory embedded with the `include_dir` crate.
+    ///
+    /// Each `.json` file at the top level of `dir` is served as an exported function named after
+    /// the file stem. No file system access is performed, as such the program and export
+    /// directories are empty.
+    ///
+    /// ## Arguments
+    ///
+    /// - `dir`: The embedded export directory.
+    ///
+    /// ## Example
+    ///
+    /// ```ignore
+    /// use include_dir::{Dir, include_dir};
+    /// use noir_runner::NoirRunner;
+    ///
+    /// static EXPORT_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/export");
+    ///
+    /// let runner = NoirRunner::from_include_dir(&EXPORT_DIR);
+    /// ```
+    #[cfg(feature = "include_dir")]
+    pub fn from_include_dir(dir: &include_dir::Dir<'static>) -> Self {
+        let artifacts = dir
+            .files()
+            .filter(|file| file.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|file| {
+                let fn_name = file.path().file_stem()?.to_str()?.to_owned();
+
+                Some((fn_name, Cow::Borrowed(file.contents())))
+            })
+            .collect();
+
+        Self::from_embedded(artifacts)
+    }
+
+    /// Constructs a [`NoirRunner`] serving a single in-memory program artifact.
+    ///
+    /// No file system access is performed, as such the program and export directories are empty.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name the program is run under.
+    /// - `artifact`: The program artifact, as produced by `nargo export`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the artifact cannot be serialized.
+    pub fn from_artifact(
+        fn_name: impl Into<String>,
+        artifact: ProgramArtifact,
+    ) -> Result<Self, Error> {
+        let bytes = serde_json::to_vec(&artifact).map_err(Error::Serde)?;
+
+        Ok(Self::from_embedded(BTreeMap::from([(
+            fn_name.into(),
+            Cow::Owned(bytes),
+        )])))
+    }
+
+    /// Constructs a [`NoirRunner`] serving a single program artifact from its JSON bytes.
+    ///
+    /// No file system access is performed, as such the program and export directories are empty.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name the program is run under.
+    /// - `bytes`: The JSON contents of the artifact, as written by `nargo export`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the bytes are not a valid program artifact.
+    pub fn from_json_bytes(fn_name: impl Into<String>, bytes: &[u8]) -> Result<Self, Error> {
+        serde_json::from_slice::<ProgramArtifact>(bytes).map_err(Error::Serde)?;
+
+        Ok(Self::from_embedded(BTreeMap::from([(
+            fn_name.into(),
+            Cow::Owned(bytes.to_vec()),
+        )])))
+    }
+
+    fn from_embedded(artifacts: BTreeMap<String, Cow<'static, [u8]>>) -> Self {
+        Self {
+            program_dir: PathBuf::new(),
+            export_directory: PathBuf::new(),
+            artifacts: Artifacts::Embedded(artifacts),
+            cache: Mutex::new(LruCache::new(None)),
+            encoders: InputEncoders::default(),
+            results: None,
+            catch_panics: false,
+            reduced_fields: false,
+            latest_version: false,
+            transforms: InputTransforms::default(),
+            numbers: NumberStrategy::default(),
+            max_brillig_steps: None,
+            report_diagnostics: true,
+            prover_binary: PathBuf::from("bb"),
+            from_manifest: false,
+        }
     }
 
     /// Runs the Noir program with the given function name and input map.
@@ -82,68 +254,2284 @@ impl NoirRunner {
     ///
     /// Note that if execution itself fails, we use nargo's diagnostic system to attempt to diagnose
     /// the error.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self, input_map))
+    )]
     pub fn run(
         &self,
         fn_name: &str,
         input_map: BTreeMap<String, InputValue>,
     ) -> Result<Option<InputValue>, Error> {
-        let fn_path = self.export_directory.join(format!("{fn_name}.json"));
+        let program = self.load_program(fn_name)?;
 
-        let reader = BufReader::new(File::open(fn_path).map_err(Error::Io)?);
+        self.execute(&program, input_map, &Bn254BlackBoxSolver(false))
+    }
 
-        let program: CompiledProgram = serde_json::from_reader::<_, ProgramArtifact>(reader)
-            .map_err(Error::Serde)
-            .unwrap()
-            .into();
+    /// Runs the Noir program with its inputs given as the fields of a single value.
+    ///
+    /// The value is converted with the runner's [`NumberStrategy`] and each of its top-level
+    /// fields becomes the input of the parameter of the same name, as nargo maps the entries of a
+    /// `Prover.toml`. Structs, maps and JSON objects are accepted.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input`: The inputs, as a value with one field per parameter.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if the value does not convert to a struct, such as a bare
+    /// integer, an error if it fails to convert, or an error in the same cases as
+    /// [`NoirRunner::run`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use noir_runner::{NoirRunner, ToNoir};
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Addition {
+    ///     x: u64,
+    ///     y: u64,
+    /// }
+    ///
+    /// let runner = NoirRunner::try_new(std::path::PathBuf::from("tests")).unwrap();
+    ///
+    /// let result = runner.run_with_input("addition", &Addition { x: 2, y: 3 }).unwrap();
+    ///
+    /// assert_eq!(result, Some(5u64.to_noir()));
+    /// ```
+    pub fn run_with_input<T: Serialize + ?Sized>(
+        &self,
+        fn_name: &str,
+        input: &T,
+    ) -> Result<Option<InputValue>, Error> {
+        let input_map = match self.to_input_value(input)? {
+            InputValue::Struct(fields) => fields,
+            value => {
+                return Err(Error::InvalidInput {
+                    param: fn_name.to_owned(),
+                    reason: format!(
+                        "expected a struct of parameters, found a {}",
+                        variant_name(&value)
+                    ),
+                });
+            }
+        };
 
-        let solved_witness_stack = execute_program(
-            &program.program,
-            program.abi.encode(&input_map, None).map_err(Error::Abi)?,
-            &Bn254BlackBoxSolver(false),
-            &mut DefaultForeignCallBuilder::default().build(),
-        );
+        self.run(fn_name, input_map)
+    }
 
-        let solved_witness_stack = solved_witness_stack
-            .map_err(|err| Self::diagnose_nargo_error(&program, err))
-            .map_err(|err| format!("{err:?}"))
-            .map_err(Error::Nargo)?;
+    /// Runs the Noir program, solving black box functions with the given solver.
+    ///
+    /// [`NoirRunner::run`] uses `Bn254BlackBoxSolver`, this allows a differently configured
+    /// solver, or a test double recording the black box calls, to be used instead. Runs are never
+    /// answered from the result cache, so the solver is always called.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    /// - `blackbox_solver`: The solver for the program's black box function calls.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error in the same cases as [`NoirRunner::run`].
+    pub fn run_with_solver<B: BlackBoxFunctionSolver<FieldElement>>(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+        blackbox_solver: &B,
+    ) -> Result<Option<InputValue>, Error> {
+        let program = self.load_program(fn_name)?;
+        let initial_witness = self.encode_inputs(&program, input_map)?;
 
-        let result = solved_witness_stack
-            .peek()
-            .map(|witness| &witness.witness)
-            .map(|witness| program.abi.decode(witness).map_err(Error::Abi))
-            .transpose()?
-            .map(|result| result.1)
-            .flatten();
+        self.solve(&program, initial_witness, blackbox_solver)
+    }
 
-        Ok(result)
+    /// Runs the Noir program read from an artifact at the given path.
+    ///
+    /// The artifact may be anywhere, bypassing the `{fn_name}.json` lookup in the export
+    /// directory, such as a standalone artifact produced by a separate build step. It is read on
+    /// every call and never cached. The program directory is still used by foreign calls that
+    /// resolve relative paths.
+    ///
+    /// ## Arguments
+    ///
+    /// - `artifact_path`: The path to the program artifact, as written by `nargo export` or
+    ///   `nargo compile`.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] if the artifact cannot be read, [`Error::VersionMismatch`] or
+    /// [`Error::Serde`] if it cannot be deserialized, or an error in the same cases as
+    /// [`NoirRunner::run`].
+    pub fn run_artifact(
+        &self,
+        artifact_path: &Path,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<Option<InputValue>, Error> {
+        let bytes = std::fs::read(artifact_path).map_err(Error::Io)?;
+        let program = parse_program(&bytes)?;
+
+        self.execute(&program, input_map, &Bn254BlackBoxSolver(false))
     }
 
-    /// Returns the program directory.
-    pub fn program_dir(&self) -> &PathBuf {
-        &self.program_dir
+    /// Compiles a Noir source snippet in memory and runs one of its functions, skipping
+    /// `nargo export`.
+    ///
+    /// The source is a single file depending only on the standard library. The function must be
+    /// marked `#[export]`, or be named `main`. The source is compiled on every call and never
+    /// cached, export the program for repeated runs.
+    ///
+    /// ## Arguments
+    ///
+    /// - `source`: The Noir source code.
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Compile`] if the source fails to compile, [`Error::FunctionNotFound`] if
+    /// it has no such function, or an error in the same cases as [`NoirRunner::run`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use noir_runner::{NoirRunner, ToNoir};
+    ///
+    /// use std::collections::BTreeMap;
+    ///
+    /// let runner = NoirRunner::try_new(std::path::PathBuf::from("tests")).unwrap();
+    ///
+    /// let source = "#[export] fn double(x: Field) -> Field { x * 2 }";
+    /// let input_map = BTreeMap::from([("x".to_owned(), 21u64.to_noir())]);
+    ///
+    /// let result = runner.compile_and_run(source, "double", input_map).unwrap();
+    ///
+    /// assert_eq!(result, Some(42u64.to_noir()));
+    /// ```
+    #[cfg(feature = "compile")]
+    pub fn compile_and_run(
+        &self,
+        source: &str,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<Option<InputValue>, Error> {
+        let program = crate::compile::compile_function(source, fn_name)?;
+
+        self.execute(&program, input_map, &Bn254BlackBoxSolver(false))
     }
 
-    /// Returns the export directory.
-    pub fn export_directory(&self) -> &PathBuf {
-        &self.export_directory
+    /// Runs the Noir program and returns the solved witness stack in its serialized form.
+    ///
+    /// The witness stack is serialized with bincode and compressed with gzip, the canonical
+    /// format of `WitnessStack`, which is byte-for-byte what `nargo execute` writes to its `.gz`
+    /// witness file. This allows the witness to be handed to other in-process components without
+    /// going through the filesystem.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded, the inputs cannot be encoded, the program
+    /// fails to execute or the witness stack cannot be serialized.
+    pub fn run_to_witness_bytes(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<Vec<u8>, Error> {
+        let program = self.load_program(fn_name)?;
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let solved_witness_stack =
+            self.solve_witness_stack(&program, initial_witness, &Bn254BlackBoxSolver(false))?;
+
+        Vec::<u8>::try_from(solved_witness_stack).map_err(|err| Error::Nargo(format!("{err:?}")))
     }
 
-    fn diagnose_nargo_error(
-        program: &CompiledProgram,
-        err: NargoError<FieldElement>,
-    ) -> NargoError<FieldElement> {
-        if let Some(diagnostic) = try_to_diagnose_runtime_error(&err, &program.abi, &program.debug)
-        {
-            diagnostic.report(
-                &DebugArtifact {
-                    debug_symbols: program.debug.clone(),
-                    file_map: program.file_map.clone(),
-                },
-                false,
-            );
+    /// Runs the Noir program and writes the solved witness stack to a file.
+    ///
+    /// The file holds the witness stack in the format `nargo execute` writes to its `.gz` witness
+    /// file, refer to [`NoirRunner::run_to_witness_bytes`], so it can be handed to a proving
+    /// backend such as `bb`. Runs are never answered from the result cache, since the witness is
+    /// needed.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    /// - `out_path`: The path of the witness file, conventionally ending in `.gz`.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be written, or an error in the same cases as
+    /// [`NoirRunner::run_to_witness_bytes`].
+    pub fn run_and_save_witness(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+        out_path: &Path,
+    ) -> Result<Option<InputValue>, Error> {
+        let program = self.load_program(fn_name)?;
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let solved_witness_stack =
+            self.solve_witness_stack(&program, initial_witness, &Bn254BlackBoxSolver(false))?;
+
+        let result = Self::decode_return(&program, &solved_witness_stack)?;
+
+        let bytes = Vec::<u8>::try_from(solved_witness_stack)
+            .map_err(|err| Error::Nargo(format!("{err:?}")))?;
+
+        std::fs::write(out_path, bytes).map_err(Error::Io)?;
+
+        Ok(result)
+    }
+
+    /// Runs the Noir program and proves its execution with the Barretenberg prover.
+    ///
+    /// The program is solved in-process, then its bytecode and witness are handed to the `bb`
+    /// binary, refer to [`NoirRunnerBuilder::prover_binary`], which generates an UltraHonk proof.
+    /// The public inputs are read from the solved witness, so they do not depend on the prover's
+    /// output format.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to prove.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the proof along with the public inputs of the main circuit.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Prover`] if the prover cannot be run or fails, or an error in the same
+    /// cases as [`NoirRunner::run`].
+    pub fn prove(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<Proof, Error> {
+        let program = self.load_program(fn_name)?;
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let solved_witness_stack =
+            self.solve_witness_stack(&program, initial_witness, &Bn254BlackBoxSolver(false))?;
+
+        let public_inputs = public_inputs(&program, &solved_witness_stack)?;
+        let witness = Vec::<u8>::try_from(solved_witness_stack)
+            .map_err(|err| Error::Nargo(format!("{err:?}")))?;
+
+        let dir = ProverDir::new()?;
+        let bytecode_path = dir.write_program(&program)?;
+        let witness_path = dir.write("witness.gz", &witness)?;
+
+        run_prover(
+            &self.prover_binary,
+            [
+                OsStr::new("prove"),
+                OsStr::new("--scheme"),
+                OsStr::new("ultra_honk"),
+                OsStr::new("-b"),
+                bytecode_path.as_os_str(),
+                OsStr::new("-w"),
+                witness_path.as_os_str(),
+                OsStr::new("-o"),
+                dir.path().as_os_str(),
+            ],
+        )?
+        .map_err(ProverFailure::into_error)?;
+
+        Ok(Proof {
+            proof: dir.read("proof")?,
+            public_inputs,
+        })
+    }
+
+    /// Verifies a proof of the Noir program with the Barretenberg prover.
+    ///
+    /// The verification key is derived from the program's bytecode on every call.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function the proof is for.
+    /// - `proof`: The proof, as returned by [`NoirRunner::prove`].
+    /// - `public_inputs`: The public inputs the proof is checked against.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `true` if the proof is valid for the public inputs.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded, or [`Error::Prover`] if the prover cannot
+    /// be run, fails to derive the verification key or fails to check the proof, such as on a
+    /// malformed proof. A proof the prover rejects is not an error.
+    pub fn verify(
+        &self,
+        fn_name: &str,
+        proof: &[u8],
+        public_inputs: &[FieldElement],
+    ) -> Result<bool, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let dir = ProverDir::new()?;
+        let bytecode_path = dir.write_program(&program)?;
+        let proof_path = dir.write("proof", proof)?;
+        let public_inputs_path = dir.write_fields("public_inputs", public_inputs)?;
+
+        run_prover(
+            &self.prover_binary,
+            [
+                OsStr::new("write_vk"),
+                OsStr::new("--scheme"),
+                OsStr::new("ultra_honk"),
+                OsStr::new("-b"),
+                bytecode_path.as_os_str(),
+                OsStr::new("-o"),
+                dir.path().as_os_str(),
+            ],
+        )?
+        .map_err(ProverFailure::into_error)?;
+
+        let vk_path = dir.path().join("vk");
+
+        let verified = run_prover(
+            &self.prover_binary,
+            [
+                OsStr::new("verify"),
+                OsStr::new("--scheme"),
+                OsStr::new("ultra_honk"),
+                OsStr::new("-k"),
+                vk_path.as_os_str(),
+                OsStr::new("-p"),
+                proof_path.as_os_str(),
+                OsStr::new("-i"),
+                public_inputs_path.as_os_str(),
+            ],
+        )?;
+
+        match verified {
+            Ok(()) => Ok(true),
+            Err(failure) if failure.is_rejection() => Ok(false),
+            Err(failure) => Err(failure.into_error()),
         }
+    }
+
+    /// Runs the Noir program, resolving foreign calls with the given executor.
+    ///
+    /// This allows circuits calling `#[oracle(..)]` functions to be tested with mocked responses.
+    /// [`NoirRunner::run`] resolves foreign calls with nargo's default executor instead. Runs are
+    /// never answered from the result cache, as the executor may respond differently each time.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    /// - `executor`: The executor resolving the program's foreign calls.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error in the same cases as [`NoirRunner::run`], including if the executor fails
+    /// to resolve a foreign call.
+    pub fn run_with_foreign_calls<E: ForeignCallExecutor<FieldElement>>(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+        executor: &mut E,
+    ) -> Result<Option<InputValue>, Error> {
+        let program = self.load_program(fn_name)?;
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let solved_witness_stack = self.solve_witness_stack_with(
+            &program,
+            initial_witness,
+            &Bn254BlackBoxSolver(false),
+            executor,
+        )?;
+
+        Self::decode_return(&program, &solved_witness_stack)
+    }
+
+    /// Runs the Noir program, capturing the lines printed with `print` and `println`.
+    ///
+    /// Printed output is collected rather than written to stdout, and split into lines. Output
+    /// printed with `print` is joined with the following output up to the next newline.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the decoded output value and the printed lines, in order.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error in the same cases as [`NoirRunner::run_with_foreign_calls`].
+    pub fn run_capturing_prints(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<(Option<InputValue>, Vec<String>), Error> {
+        let mut output = String::new();
+
+        let result = self.run_with_foreign_calls(
+            fn_name,
+            input_map,
+            &mut DefaultForeignCallBuilder::default()
+                .with_output(PrintOutput::String(&mut output))
+                .build(),
+        )?;
+
+        Ok((result, output.lines().map(ToOwned::to_owned).collect()))
+    }
+
+    /// Runs the Noir program and returns the decoded output value along with the solved witness
+    /// stack.
+    ///
+    /// The witness stack holds the complete witness of every circuit executed, useful for
+    /// debugging or for handing to a separate proving step. Runs are never answered from the result
+    /// cache, since the witness is needed.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error in the same cases as [`NoirRunner::run`].
+    pub fn run_with_witness(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<(Option<InputValue>, WitnessStack<FieldElement>), Error> {
+        let program = self.load_program(fn_name)?;
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let solved_witness_stack =
+            self.solve_witness_stack(&program, initial_witness, &Bn254BlackBoxSolver(false))?;
+
+        let result = Self::decode_return(&program, &solved_witness_stack)?;
+
+        Ok((result, solved_witness_stack))
+    }
+
+    /// Runs the Noir program from an initial witness, bypassing the ABI encoding of inputs.
+    ///
+    /// The witness is handed to the solver as is, the registered encoders, transforms and input
+    /// validation are skipped. This allows supplying values the ABI encoder cannot express, such
+    /// as a precomputed or partially solved witness. Runs are never answered from the result
+    /// cache.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `initial_witness`: The initial witness of the main circuit, holding at least the values
+    ///   of its parameters.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error in the same cases as [`NoirRunner::run`], except for input encoding.
+    pub fn run_with_initial_witness(
+        &self,
+        fn_name: &str,
+        initial_witness: WitnessMap<FieldElement>,
+    ) -> Result<Option<InputValue>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        self.solve(&program, initial_witness, &Bn254BlackBoxSolver(false))
+    }
+
+    /// Runs the Noir program and returns the decoded output value along with the public inputs of
+    /// its main circuit.
+    ///
+    /// The public inputs are the witness values of the public parameters, refer to
+    /// [`NoirRunner::public_parameters`], and of the return values, in witness order. This is the
+    /// list a proof of the execution is verified against, refer to [`NoirRunner::prove`].
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error in the same cases as [`NoirRunner::run`]. Runs are never answered from the
+    /// result cache, since the witness is needed.
+    pub fn run_with_public_inputs(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<(Option<InputValue>, Vec<FieldElement>), Error> {
+        let program = self.load_program(fn_name)?;
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let solved_witness_stack =
+            self.solve_witness_stack(&program, initial_witness, &Bn254BlackBoxSolver(false))?;
+
+        let result = Self::decode_return(&program, &solved_witness_stack)?;
+
+        Ok((result, public_inputs(&program, &solved_witness_stack)?))
+    }
+
+    /// Runs the Noir program and returns its inputs, as decoded from the solved witness, along with
+    /// the decoded output value.
+    ///
+    /// The inputs are decoded with the ABI from the same witness as the output, so they reflect
+    /// exactly what the solver saw after the registered encoders and transforms were applied.
+    /// Comparing them to the original inputs detects any surprises in encoding.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error in the same cases as [`NoirRunner::run`]. Runs are never answered from
+    /// the result cache, since the witness is needed to decode the inputs.
+    pub fn run_echo(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<(BTreeMap<String, InputValue>, Option<InputValue>), Error> {
+        let program = self.load_program(fn_name)?;
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let solved_witness_stack =
+            self.solve_witness_stack(&program, initial_witness, &Bn254BlackBoxSolver(false))?;
+
+        let witness = solved_witness_stack
+            .peek()
+            .map(|witness| &witness.witness)
+            .ok_or_else(|| Error::Nargo("empty witness stack".to_owned()))?;
+
+        let (mut inputs, mut result) = program.abi.decode(witness).map_err(Error::Abi)?;
+
+        decode_signed_inputs(&program.abi, &mut inputs);
+
+        if let (Some(return_type), Some(result)) = (&program.abi.return_type, &mut result) {
+            decode_signed(&return_type.abi_type, result);
+        }
+
+        Ok((inputs, result))
+    }
+
+    /// Checks that the inputs would encode for the Noir program, without executing it.
+    ///
+    /// The program is loaded, the registered encoders and transforms are applied, and the inputs
+    /// are validated and encoded with the ABI exactly as for [`NoirRunner::run`]. Execution is
+    /// skipped, so this is much faster than a full run but does not check the program's
+    /// constraints.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to check the inputs of.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded or the inputs cannot be encoded.
+    pub fn dry_run(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<(), Error> {
+        self.check_inputs(fn_name, input_map).map(|_| ())
+    }
+
+    /// Encodes the inputs for the Noir program, without executing it.
+    ///
+    /// This performs the same checks as [`NoirRunner::dry_run`] and returns the encoded initial
+    /// witness, which can be inspected or run later with [`NoirRunner::run_with_initial_witness`].
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to encode the inputs of.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the initial witness of the main circuit, mapping its parameter witnesses to the
+    /// encoded input values.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded or the inputs cannot be encoded.
+    pub fn check_inputs(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<WitnessMap<FieldElement>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        self.encode_inputs(&program, input_map)
+    }
+
+    /// Runs the Noir program, reporting unsatisfied constraints as an outcome rather than an error.
+    ///
+    /// This suits validation-style use, where inputs failing the program's constraints are an
+    /// expected result. Errors are reserved for failures to load the program, encode the inputs,
+    /// resolve foreign calls or decode the output.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Returns
+    ///
+    /// Returns [`RunOutcome::Satisfied`] with the decoded output value if execution succeeds, or
+    /// [`RunOutcome::Unsatisfied`] with the reason if a constraint fails.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error in the same cases as [`NoirRunner::run`], except for failed constraints.
+    pub fn try_run(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<RunOutcome, Error> {
+        let program = self.load_program(fn_name)?;
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let solved_witness_stack = self.try_solve_witness_stack(
+            &program,
+            initial_witness,
+            &Bn254BlackBoxSolver(false),
+            &mut DefaultForeignCallBuilder::default().build(),
+            None,
+        )?;
+
+        match solved_witness_stack {
+            Ok(solved_witness_stack) => {
+                Self::decode_return(&program, &solved_witness_stack).map(RunOutcome::Satisfied)
+            }
+            Err(NargoError::ExecutionError(err)) => Ok(RunOutcome::Unsatisfied(err.to_string())),
+            Err(err) => Err(Error::Nargo(format!("{err:?}"))),
+        }
+    }
+
+    /// Runs the Noir program and records the field elements each parameter was encoded into.
+    ///
+    /// The log maps each parameter name to its field elements in witness order, as laid out by the
+    /// ABI. It is recorded as soon as the inputs are encoded, so it is available for audit
+    /// trails even if execution fails.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the result of the run, as returned by [`NoirRunner::run`], along with the input
+    /// log. The log is empty if the program could not be loaded or the inputs could not be
+    /// encoded.
+    pub fn run_with_input_log(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> (
+        Result<Option<InputValue>, Error>,
+        BTreeMap<String, Vec<FieldElement>>,
+    ) {
+        let mut input_log = BTreeMap::new();
+
+        let result = self.load_program(fn_name).and_then(|program| {
+            let initial_witness = self.encode_inputs(&program, input_map)?;
+
+            input_log = Self::input_log(&program, &initial_witness);
+
+            self.solve(&program, initial_witness, &Bn254BlackBoxSolver(false))
+        });
+
+        (result, input_log)
+    }
+
+    /// Runs a batch of jobs, pairing each result with the caller's request identifier.
+    ///
+    /// Each job is a request identifier, a function name and an input map. Jobs are run in order
+    /// and a failing job does not stop the rest of the batch, its error is returned alongside its
+    /// identifier instead. The identifiers allow results to be correlated with their requests
+    /// once they are dispatched, regardless of the order they are processed in.
+    ///
+    /// ## Arguments
+    ///
+    /// - `jobs`: The jobs to run, as `(request_id, fn_name, input_map)` tuples.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the result of each job, as returned by [`NoirRunner::run`], with its identifier.
+    pub fn run_keyed<Id>(
+        &self,
+        jobs: Vec<(Id, String, BTreeMap<String, InputValue>)>,
+    ) -> Vec<(Id, Result<Option<InputValue>, Error>)> {
+        jobs.into_iter()
+            .map(|(request_id, fn_name, input_map)| (request_id, self.run(&fn_name, input_map)))
+            .collect()
+    }
+
+    /// Runs the Noir program once for each input map, loading the program only once.
+    ///
+    /// The program and black box solver are shared by every run, as with a [`RunSession`], and
+    /// each run resolves its foreign calls with its own executor.
+    ///
+    /// With the `rayon` feature, the input maps are run concurrently on the rayon thread pool.
+    /// This requires the black box solver to be shared between threads, which
+    /// `Bn254BlackBoxSolver` allows as it holds no state between calls, and the registered input
+    /// encoders and transforms to be callable from any thread, which their `Send + Sync` bounds
+    /// ensure. Foreign call side effects, such as prints, may then interleave across runs.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `inputs`: The input maps to run the function with.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of each run, if any, in the order of `inputs`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded, or [`Error::Batch`] with the index of a
+    /// failing input map, in the same cases as [`NoirRunner::run`]. Runs stop at the first failure,
+    /// with the `rayon` feature the reported failure is any of the failing input maps.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self, inputs))
+    )]
+    pub fn run_batch(
+        &self,
+        fn_name: &str,
+        inputs: Vec<BTreeMap<String, InputValue>>,
+    ) -> Result<Vec<Option<InputValue>>, Error> {
+        let program = self.load_program(fn_name)?;
+        let blackbox_solver = Bn254BlackBoxSolver(false);
+
+        let run = |(index, input_map): (usize, BTreeMap<String, InputValue>)| {
+            self.execute(&program, input_map, &blackbox_solver)
+                .map_err(|error| Error::Batch {
+                    index,
+                    error: Box::new(error),
+                })
+        };
+
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+            inputs.into_par_iter().enumerate().map(run).collect()
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        {
+            inputs.into_iter().enumerate().map(run).collect()
+        }
+    }
+
+    /// Opens a [`RunSession`] for the given function.
+    ///
+    /// The session loads the program once and reuses it, along with the black box solver, for
+    /// every run. This makes the per-run cost encoding, solving and decoding only, independent of
+    /// the artifact cache.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use noir_runner::{NoirRunner, ToNoir};
+    ///
+    /// use std::collections::BTreeMap;
+    ///
+    /// let runner = NoirRunner::try_new(std::path::PathBuf::from("tests")).unwrap();
+    /// let session = runner.session("addition").unwrap();
+    ///
+    /// for x in 0..100i128 {
+    ///     let input_map = BTreeMap::from([
+    ///         ("x".to_owned(), x.to_noir()),
+    ///         ("y".to_owned(), 1i128.to_noir()),
+    ///     ]);
+    ///
+    ///     session.run(input_map).unwrap();
+    /// }
+    /// ```
+    pub fn session(&self, fn_name: &str) -> Result<RunSession<'_>, Error> {
+        Ok(RunSession::new(self, self.load_program(fn_name)?))
+    }
+
+    /// Runs the Noir program with inputs given as `name=value` pairs.
+    ///
+    /// Each value is parsed against the ABI type of its parameter. Fields accept decimal or `0x`
+    /// prefixed hexadecimal values, integers accept decimal or hexadecimal values within the range
+    /// of their width, booleans accept `true`, `false`, `1` or `0`, and strings are taken as is.
+    /// Arrays, tuples and structs are not supported.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `pairs`: The inputs as `name=value` pairs, such as `["x=2", "y=0x03"]`.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if a pair has no `=`, names an unknown parameter or has a value that
+    /// cannot be parsed, or in the same cases as [`NoirRunner::run`].
+    pub fn run_from_kv(&self, fn_name: &str, pairs: &[&str]) -> Result<Option<InputValue>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let input_map = pairs
+            .iter()
+            .map(|pair| {
+                let (name, value) = pair.split_once('=').ok_or_else(|| Error::InvalidInput {
+                    param: pair.to_string(),
+                    reason: "expected a `name=value` pair".to_owned(),
+                })?;
+
+                let name = name.trim();
+
+                let parameter = program
+                    .abi
+                    .parameters
+                    .iter()
+                    .find(|parameter| parameter.name == name)
+                    .ok_or_else(|| Error::UnknownParameter(name.to_owned()))?;
+
+                Ok((
+                    name.to_owned(),
+                    self.parse_parameter(name, &parameter.typ, value)?,
+                ))
+            })
+            .collect::<Result<BTreeMap<_, _>, Error>>()?;
+
+        self.execute(&program, input_map, &Bn254BlackBoxSolver(false))
+    }
+
+    /// Runs the Noir program with inputs read from environment variables.
+    ///
+    /// Each parameter is read from the variable named by the prefix followed by the parameter
+    /// name in upper case, so with the prefix `APP_` the parameter `x` is read from `APP_X`. The
+    /// values are parsed as in [`NoirRunner::run_from_kv`].
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `prefix`: The prefix of the environment variable names.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if a variable is not set, is not valid unicode or has a value that cannot
+    /// be parsed, or in the same cases as [`NoirRunner::run`].
+    pub fn run_from_env(&self, fn_name: &str, prefix: &str) -> Result<Option<InputValue>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let input_map = program
+            .abi
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let name = &parameter.name;
+                let var = format!("{prefix}{}", name.to_uppercase());
+
+                let value = std::env::var(&var).map_err(|err| Error::InvalidInput {
+                    param: name.clone(),
+                    reason: format!("environment variable `{var}`: {err}"),
+                })?;
+
+                Ok((
+                    name.clone(),
+                    self.parse_parameter(name, &parameter.typ, &value)?,
+                ))
+            })
+            .collect::<Result<BTreeMap<_, _>, Error>>()?;
+
+        self.execute(&program, input_map, &Bn254BlackBoxSolver(false))
+    }
+
+    /// Runs the Noir program and decodes the output value into a Rust type.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the decoded output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::ReturnTypeMismatch`] or [`Error::InvalidOutput`] if the output value
+    /// cannot be decoded into `T`, or an error in the same cases as [`NoirRunner::run`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use noir_runner::{NoirRunner, ToNoir};
+    ///
+    /// use std::collections::BTreeMap;
+    ///
+    /// let runner = NoirRunner::try_new(std::path::PathBuf::from("tests")).unwrap();
+    ///
+    /// let input_map = BTreeMap::from([
+    ///     ("x".to_owned(), 2u64.to_noir()),
+    ///     ("y".to_owned(), 3u64.to_noir()),
+    /// ]);
+    ///
+    /// let sum: u64 = runner.run_typed("addition", input_map).unwrap().unwrap();
+    ///
+    /// assert_eq!(sum, 5);
+    /// ```
+    pub fn run_typed<T: FromNoir>(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<Option<T>, Error> {
+        self.run(fn_name, input_map)?.map(T::from_noir).transpose()
+    }
+
+    /// Runs the Noir program and records resource high-water marks observed during execution.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any, along with the [`ResourceStats`] of the
+    /// run. Refer to the fields of [`ResourceStats`] for which statistics are exact and which are
+    /// estimated.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error in the same cases as [`NoirRunner::run`].
+    ///
+    /// Note that the program is stepped through one Brillig instruction at a time, which is slower
+    /// than [`NoirRunner::run`].
+    pub fn run_with_resource_stats(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<(Option<InputValue>, ResourceStats), Error> {
+        let program = self.load_program(fn_name)?;
+
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let mut stats = ResourceStats::default();
+
+        let solved_witness_stack =
+            self.solve_witness_stack_with_hook(&program, initial_witness, &mut stats)?;
+
+        Ok((Self::decode_return(&program, &solved_witness_stack)?, stats))
+    }
+
+    /// Runs the Noir program, aborting if execution has not completed by the given deadline.
+    ///
+    /// The deadline is checked before execution starts and after every ACIR opcode and every
+    /// Brillig instruction. Foreign calls, such as oracles, are not interrupted, so a slow foreign
+    /// call may overrun the deadline by its own duration.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    /// - `deadline`: The instant by which execution must complete.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Timeout`] if the deadline passes, or an error in the same cases as
+    /// [`NoirRunner::run`].
+    pub fn run_until(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+        deadline: Instant,
+    ) -> Result<Option<InputValue>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let mut deadline = Deadline(deadline);
+        deadline.on_acir_step()?;
+
+        let solved_witness_stack =
+            self.solve_witness_stack_with_hook(&program, initial_witness, &mut deadline)?;
+
+        Self::decode_return(&program, &solved_witness_stack)
+    }
+
+    /// Runs the Noir program, aborting if execution takes longer than the given timeout.
+    ///
+    /// This is [`NoirRunner::run_until`] with a deadline of `timeout` from now, which includes
+    /// loading the program and encoding the inputs. Cancellation is cooperative: execution runs on
+    /// the calling thread and stops at the next opcode or Brillig instruction past the deadline, so
+    /// no worker thread is spawned or leaked. The tradeoff is that a slow foreign call is not
+    /// interrupted and may overrun the timeout by its own duration.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    /// - `timeout`: The longest the run may take.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Timeout`] if the timeout elapses, or an error in the same cases as
+    /// [`NoirRunner::run_until`].
+    pub fn run_with_timeout(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+        timeout: Duration,
+    ) -> Result<Option<InputValue>, Error> {
+        // a timeout too large to represent never elapses
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.run_until(fn_name, input_map, deadline),
+            None => self.run(fn_name, input_map),
+        }
+    }
+
+    /// Runs a Noir program whose ABI is a single flat field array in and out.
+    ///
+    /// This is a shorthand for circuits such as hash chains that take a `[Field; N]` and return a
+    /// `[Field; M]`, sparing the construction of the input map and the unwrapping of the output.
+    /// The run goes through the registered encoders, input validation and result cache as with
+    /// [`NoirRunner::run`].
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `inputs`: The field elements of the input array.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the field elements of the output array.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the function does not take exactly one `[Field; N]` parameter and
+    /// return a `[Field; M]`, if `inputs` does not have length `N`, or in the same cases as
+    /// [`NoirRunner::run`].
+    pub fn run_fields(
+        &self,
+        fn_name: &str,
+        inputs: &[FieldElement],
+    ) -> Result<Vec<FieldElement>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let input_length = match program.abi.parameters.as_slice() {
+            [parameter] => Self::flat_field_array_length(&parameter.typ),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            Error::AbiMismatch(format!("{fn_name} does not take a single [Field; N]"))
+        })?;
+
+        let output_length = program
+            .abi
+            .return_type
+            .as_ref()
+            .and_then(|return_type| Self::flat_field_array_length(&return_type.abi_type))
+            .ok_or_else(|| Error::AbiMismatch(format!("{fn_name} does not return a [Field; M]")))?;
+
+        if inputs.len() != input_length as usize {
+            return Err(Error::AbiMismatch(format!(
+                "{fn_name} expects {input_length} fields, found {}",
+                inputs.len()
+            )));
+        }
+
+        let input_map = BTreeMap::from([(
+            program.abi.parameters[0].name.clone(),
+            InputValue::Vec(inputs.iter().copied().map(InputValue::Field).collect()),
+        )]);
+
+        match self.execute(&program, input_map, &Bn254BlackBoxSolver(false))? {
+            Some(InputValue::Vec(values)) if values.len() == output_length as usize => values
+                .into_iter()
+                .map(|value| match value {
+                    InputValue::Field(field) => Ok(field),
+                    value => Err(Error::ReturnTypeMismatch {
+                        expected: "Field".to_owned(),
+                        found: variant_name(&value).to_owned(),
+                    }),
+                })
+                .collect(),
+            _ => Err(Error::InvalidOutput(format!(
+                "expected {output_length} fields"
+            ))),
+        }
+    }
+
+    /// Runs a Noir program whose return type is a flat field array, yielding the returned field
+    /// elements lazily.
+    ///
+    /// The returned field elements are read out of the solved witness one at a time rather than
+    /// decoded into an [`InputValue::Vec`], which avoids a second copy of large outputs.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the function does not return a `[Field; M]`, or in the same cases as
+    /// [`NoirRunner::run`].
+    pub fn run_streaming(
+        &self,
+        fn_name: &str,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<impl Iterator<Item = FieldElement> + use<>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let output_length = program
+            .abi
+            .return_type
+            .as_ref()
+            .and_then(|return_type| Self::flat_field_array_length(&return_type.abi_type))
+            .ok_or_else(|| Error::AbiMismatch(format!("{fn_name} does not return a [Field; M]")))?;
+
+        let initial_witness = self.encode_inputs(&program, input_map)?;
+
+        let mut solved_witness_stack =
+            self.solve_witness_stack(&program, initial_witness, &Bn254BlackBoxSolver(false))?;
+
+        let witness = solved_witness_stack
+            .pop()
+            .ok_or_else(|| Error::Nargo("empty witness stack".to_owned()))?
+            .witness;
+
+        let start = program.abi.field_count();
+        let indices = start..start + output_length;
+
+        if let Some(index) = indices
+            .clone()
+            .find(|index| witness.get_index(*index).is_none())
+        {
+            return Err(Error::Nargo(format!(
+                "missing assignment for witness {index}"
+            )));
+        }
+
+        Ok(indices.map(move |index| witness[&Witness(index)]))
+    }
+
+    /// Lists the names of the exported functions available to the runner.
+    ///
+    /// Each `.json` file at the top level of the export directory is an exported function named
+    /// after the file stem, other files and subdirectories are ignored. If the runner resolves the
+    /// latest version of each function, refer to [`NoirRunnerBuilder::latest_version`], versioned
+    /// artifacts `{fn_name}.v{N}.json` are listed once, as `fn_name`.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the function names in sorted order.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the export directory does not exist or cannot be read.
+    pub fn list_functions(&self) -> Result<Vec<String>, Error> {
+        match &self.artifacts {
+            Artifacts::ExportDirectory => {
+                let entries = std::fs::read_dir(&self.export_directory).map_err(|err| {
+                    if err.kind() != std::io::ErrorKind::NotFound {
+                        return Error::Io(err);
+                    }
+
+                    Error::Io(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!(
+                            "export directory {} does not exist, run `nargo export` first",
+                            self.export_directory.display()
+                        ),
+                    ))
+                })?;
+
+                let mut functions = entries
+                    .map(|entry| entry.map(|entry| entry.path()).map_err(Error::Io))
+                    .filter_map(|path| match path {
+                        Ok(path)
+                            if path.is_file()
+                                && path.extension().is_some_and(|ext| ext == "json") =>
+                        {
+                            path.file_stem()
+                                .and_then(|stem| stem.to_str())
+                                .map(|stem| {
+                                    if self.latest_version {
+                                        unversioned_name(stem)
+                                    } else {
+                                        stem
+                                    }
+                                })
+                                .map(|name| Ok(name.to_owned()))
+                        }
+                        Ok(_) => None,
+                        Err(err) => Some(Err(err)),
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                functions.sort();
+                functions.dedup();
+
+                Ok(functions)
+            }
+            Artifacts::Embedded(artifacts) => Ok(artifacts.keys().cloned().collect()),
+        }
+    }
+
+    /// Encodes the decoded output of one Noir program as the input witness of another.
+    ///
+    /// If the function takes a single parameter, the output is passed as that parameter. Otherwise
+    /// the output must be a struct whose fields are the function's parameters. The output is
+    /// encoded with the function's ABI as is, without round-tripping through Rust types.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to encode the input witness for.
+    /// - `output`: The decoded output of the previous function.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded, the output does not match the function's
+    /// parameters, or the output cannot be encoded.
+    pub fn encode_chained(
+        &self,
+        fn_name: &str,
+        output: InputValue,
+    ) -> Result<WitnessMap<FieldElement>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let input_map = Self::chained_input_map(&program, fn_name, output)?;
+
+        self.encode_inputs(&program, input_map)
+    }
+
+    /// Runs a Noir program with the decoded output of another program as its input.
+    ///
+    /// Refer to [`NoirRunner::encode_chained`] for how the output is mapped to the function's
+    /// parameters.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to run.
+    /// - `output`: The decoded output of the previous function.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the output does not match the function's parameters, or in the same
+    /// cases as [`NoirRunner::run`].
+    pub fn run_chained(
+        &self,
+        fn_name: &str,
+        output: InputValue,
+    ) -> Result<Option<InputValue>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let input_map = Self::chained_input_map(&program, fn_name, output)?;
+
+        self.execute(&program, input_map, &Bn254BlackBoxSolver(false))
+    }
+
+    /// Returns the program's ABI, describing its parameters and return type.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to inspect.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded, such as [`Error::FunctionNotFound`] if
+    /// the function's artifact does not exist.
+    pub fn get_abi(&self, fn_name: &str) -> Result<Abi, Error> {
+        Ok(self.load_program(fn_name)?.abi.clone())
+    }
+
+    /// Serializes the program's ABI to JSON.
+    ///
+    /// The JSON contains the parameter names, types and visibilities, the return type, and the
+    /// error types, in the same format as the `abi` field of the exported artifact.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to inspect.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded or the ABI cannot be serialized.
+    pub fn abi_json(&self, fn_name: &str) -> Result<String, Error> {
+        let program = self.load_program(fn_name)?;
+
+        serde_json::to_string(&program.abi).map_err(Error::Serde)
+    }
+
+    /// Renders the program's ABI as a human-readable signature.
+    ///
+    /// For example, the `addition` function renders as `addition(x: Field, y: Field) -> Field`.
+    /// Public parameters and return values are prefixed with `pub`.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to inspect.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded.
+    pub fn signature(&self, fn_name: &str) -> Result<String, Error> {
+        let program = self.load_program(fn_name)?;
+
+        Ok(render_signature(fn_name, &program.abi))
+    }
+
+    /// Returns the ABI type of the program's return value.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to inspect.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the return type, or `None` if the function does not return a value.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded.
+    pub fn return_type(&self, fn_name: &str) -> Result<Option<AbiType>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        Ok(program
+            .abi
+            .return_type
+            .as_ref()
+            .map(|return_type| return_type.abi_type.clone()))
+    }
+
+    /// Returns the number of field elements the program returns, derived from its ABI return type.
+    ///
+    /// This is the length of the output of [`NoirRunner::run_fields`], so output buffers can be
+    /// pre-allocated before running. Programs without a return value return `0`.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to inspect.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded.
+    pub fn return_witness_count(&self, fn_name: &str) -> Result<usize, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let count = program
+            .abi
+            .return_type
+            .as_ref()
+            .map_or(0, |return_type| return_type.abi_type.field_count());
+
+        Ok(count as usize)
+    }
+
+    /// Lists the program's public parameters, as declared with `pub` in the circuit.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to inspect.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the names of the public parameters, in declaration order. The remaining parameters
+    /// are private.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded.
+    pub fn public_parameters(&self, fn_name: &str) -> Result<Vec<String>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let parameters = program
+            .abi
+            .parameters
+            .iter()
+            .filter(|parameter| parameter.is_public())
+            .map(|parameter| parameter.name.clone())
+            .collect();
+
+        Ok(parameters)
+    }
+
+    /// Reports which of the program's parameters could be left blank and zero-defaulted.
+    ///
+    /// Fields, integers and booleans are defaultable, as zero is a valid value of each. Arrays,
+    /// tuples and structs are defaultable if all their elements are. Strings are never
+    /// defaultable, as a string of zero bytes is rarely a meaningful value, so any parameter
+    /// containing a string requires an explicit value.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to inspect.
+    ///
+    /// ## Returns
+    ///
+    /// Returns whether each parameter is defaultable, keyed by parameter name.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded.
+    pub fn defaultable_parameters(&self, fn_name: &str) -> Result<BTreeMap<String, bool>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let parameters = program
+            .abi
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.name.clone(), Self::is_defaultable(&parameter.typ)))
+            .collect();
+
+        Ok(parameters)
+    }
+
+    /// Returns the version of nargo the function's artifact was compiled with.
+    ///
+    /// Only the version is deserialized, the program itself is skipped over and the artifact
+    /// cache is left untouched. This allows tooling to detect circuits compiled with a different
+    /// version than the runner supports.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to inspect.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the artifact cannot be read or has no version.
+    pub fn artifact_nargo_version(&self, fn_name: &str) -> Result<String, Error> {
+        Ok(self.read_artifact::<ArtifactVersion>(fn_name)?.noir_version)
+    }
+
+    /// Lists the distinct black box functions invoked by the program's ACIR opcodes.
+    ///
+    /// Note that black box operations performed by unconstrained (Brillig) functions are not
+    /// included, as they are executed by the Brillig VM rather than by the ACIR solver.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to inspect.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the black box function names, such as `blake3` or `range`, in sorted order.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded.
+    pub fn black_box_functions(&self, fn_name: &str) -> Result<Vec<String>, Error> {
+        let program = self.load_program(fn_name)?;
+
+        let functions = program
+            .program
+            .functions
+            .iter()
+            .flat_map(|circuit| &circuit.opcodes)
+            .filter_map(|opcode| match opcode {
+                Opcode::BlackBoxFuncCall(call) => Some(call.get_black_box_func().name().to_owned()),
+                _ => None,
+            })
+            .collect::<BTreeSet<_>>();
+
+        Ok(functions.into_iter().collect())
+    }
+
+    /// Measures the static size of the program.
+    ///
+    /// The metrics are derived from the compiled bytecode alone, without running the program, so
+    /// they can be compared across revisions of a circuit to spot unexpected growth. The number of
+    /// Brillig instructions executed by a particular run is reported by
+    /// [`NoirRunner::run_with_resource_stats`].
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to inspect.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded.
+    pub fn metrics(&self, fn_name: &str) -> Result<CircuitMetrics, Error> {
+        let program = self.load_program(fn_name)?;
+
+        Ok(CircuitMetrics::new(&program.program))
+    }
+
+    /// Loads every exported function and checks that its ABI is consistent with its bytecode.
+    ///
+    /// Unlike running each function, this does not stop at the first failure. Artifacts are read
+    /// from their source rather than from the cache, so the report reflects the current exports.
+    ///
+    /// A function passes if its artifact deserializes, its parameter names are unique, and its
+    /// ABI describes as many fields as the main circuit has parameter witnesses.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the exported functions cannot be listed.
+    pub fn validate_all(&self) -> Result<ValidationReport, Error> {
+        let results = self
+            .list_functions()?
+            .into_iter()
+            .map(|fn_name| {
+                let result = self.read_program(&fn_name).and_then(|program| {
+                    Self::validate_program(&program).map_err(Error::AbiMismatch)
+                });
+
+                (fn_name, result)
+            })
+            .collect();
+
+        Ok(ValidationReport { results })
+    }
+
+    /// Loads every exported function into the artifact cache, skipping those that fail to load.
+    ///
+    /// Unlike running each function, a broken artifact does not stop the remaining functions from
+    /// loading, so a service can start with its healthy circuits and report the broken ones. Use
+    /// [`ValidationReport::successes`] to list the functions that loaded. Note that if the cache
+    /// is bounded, loading more functions than its capacity evicts the least recently loaded.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the exported functions cannot be listed.
+    pub fn preload_lenient(&self) -> Result<ValidationReport, Error> {
+        let results = self
+            .list_functions()?
+            .into_iter()
+            .map(|fn_name| {
+                let result = self.load_program(&fn_name).map(|_| ());
+
+                (fn_name, result)
+            })
+            .collect();
+
+        Ok(ValidationReport { results })
+    }
+
+    /// Converts a value into an [`InputValue`], converting numbers with the runner's
+    /// [`NumberStrategy`].
+    ///
+    /// This behaves like [`ToNoir::try_to_noir`], which always uses the default strategy, refer to
+    /// [`NoirRunnerBuilder::number_strategy`] to configure the runner's.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::NonFiniteFloat`] if the value contains a NaN or infinite float, the error
+    /// of the number strategy if it rejects a number, or [`Error::InvalidValue`] if the value
+    /// fails to serialize.
+    ///
+    /// [`ToNoir::try_to_noir`]: crate::ToNoir::try_to_noir
+    pub fn to_input_value<T: Serialize + ?Sized>(&self, value: &T) -> Result<InputValue, Error> {
+        to_input_value_with(value, &self.numbers)
+    }
+
+    /// Returns the program directory.
+    pub fn program_dir(&self) -> &PathBuf {
+        &self.program_dir
+    }
+
+    /// Returns the export directory.
+    pub fn export_directory(&self) -> &PathBuf {
+        &self.export_directory
+    }
+
+    /// Loads the function into the artifact cache and exempts it from eviction.
+    ///
+    /// A pinned program stays cached regardless of [`NoirRunnerBuilder::max_cached_artifacts`],
+    /// so latency-critical functions never pay for reading their artifact again. Pinned programs
+    /// count towards the capacity, the remaining entries are evicted as usual.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to pin.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the program cannot be loaded.
+    pub fn pin(&self, fn_name: &str) -> Result<(), Error> {
+        let program = self.load_program(fn_name)?;
+
+        let mut cache = self.cache();
+
+        // the program may have been evicted by another thread since it was loaded
+        if !cache.pin(fn_name) {
+            let pinned = cache.insert_pinned(fn_name.to_owned(), program);
+
+            debug_assert!(pinned, "pinned entries are never evicted");
+        }
+
+        Ok(())
+    }
+
+    /// Makes a function pinned with [`NoirRunner::pin`] evictable again.
+    ///
+    /// Unpinning a function that is not pinned or not cached has no effect.
+    ///
+    /// ## Arguments
+    ///
+    /// - `fn_name`: The name of the function to unpin.
+    pub fn unpin(&self, fn_name: &str) {
+        self.cache().unpin(fn_name);
+    }
+
+    /// Removes every program from the artifact cache, including pinned programs.
+    ///
+    /// Each function is read from its artifact again on its next run, use this after the export
+    /// directory is regenerated. Memoized results are keyed by program hash, so they are never
+    /// returned for a changed program and are left untouched.
+    pub fn clear_cache(&self) {
+        self.cache().clear();
+    }
+
+    /// Reloads the runner after the circuits are re-exported.
+    ///
+    /// Clears the artifact cache, as with [`NoirRunner::clear_cache`], so every function is read
+    /// from its regenerated artifact on its next run. If the export directory was derived from the
+    /// `Nargo.toml` manifest, the manifest is resolved again to check that it is still valid and
+    /// still exports to the same directory. The export directory itself is fixed once the runner
+    /// is constructed, construct a new runner if it moves.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::NargoManifest`] if the manifest no longer resolves, or
+    /// [`Error::Io`] if it now resolves to a different export directory. The cache is cleared in
+    /// either case.
+    pub fn reload(&self) -> Result<(), Error> {
+        self.clear_cache();
+
+        if !self.from_manifest {
+            return Ok(());
+        }
+
+        let export_directory = resolve_export_directory(&self.program_dir)?;
+
+        if export_directory != self.export_directory {
+            return Err(Error::Io(std::io::Error::other(format!(
+                "export directory moved from {} to {}, construct a new runner",
+                self.export_directory.display(),
+                export_directory.display()
+            ))));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of runs answered from the result cache.
+    ///
+    /// Always returns `0` unless result memoization is enabled with
+    /// [`NoirRunnerBuilder::memoize_results`].
+    pub fn result_cache_hits(&self) -> usize {
+        self.results.as_ref().map_or(0, ResultCache::hits)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub(crate) fn load_program(&self, fn_name: &str) -> Result<Arc<CompiledProgram>, Error> {
+        if let Some(program) = self.cache().get(fn_name) {
+            return Ok(program);
+        }
+
+        let program = Arc::new(self.read_program(fn_name)?);
+
+        self.cache().insert(fn_name.to_owned(), program.clone());
+
+        Ok(program)
+    }
+
+    fn cache(&self) -> MutexGuard<'_, LruCache<String, Arc<CompiledProgram>>> {
+        // the cache is never left in an inconsistent state, so a poisoned lock is recovered
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn read_program(&self, fn_name: &str) -> Result<CompiledProgram, Error> {
+        parse_program(&self.artifact_bytes(fn_name)?)
+    }
+
+    /// Deserializes the function's artifact, or the parts of it that `T` describes.
+    fn read_artifact<T: DeserializeOwned>(&self, fn_name: &str) -> Result<T, Error> {
+        serde_json::from_slice(&self.artifact_bytes(fn_name)?).map_err(Error::Serde)
+    }
+
+    /// Reads the JSON contents of the function's artifact.
+    fn artifact_bytes(&self, fn_name: &str) -> Result<Cow<'_, [u8]>, Error> {
+        match &self.artifacts {
+            Artifacts::ExportDirectory => {
+                let fn_path = if self.latest_version {
+                    self.latest_artifact_path(fn_name)?
+                } else {
+                    self.export_directory.join(format!("{fn_name}.json"))
+                };
+
+                let bytes =
+                    std::fs::read(fn_path).map_err(|err| self.missing_artifact(fn_name, err))?;
+
+                Ok(Cow::Owned(bytes))
+            }
+            Artifacts::Embedded(artifacts) => {
+                let bytes = artifacts
+                    .get(fn_name)
+                    .ok_or_else(|| self.function_not_found(fn_name))?;
+
+                Ok(Cow::Borrowed(bytes))
+            }
+        }
+    }
+
+    /// Describes a function whose artifact could not be opened, listing the available functions if
+    /// it does not exist so that misspelled names are easy to spot.
+    fn missing_artifact(&self, fn_name: &str, err: std::io::Error) -> Error {
+        if err.kind() != std::io::ErrorKind::NotFound {
+            return Error::Io(err);
+        }
+
+        self.function_not_found(fn_name)
+    }
+
+    fn function_not_found(&self, fn_name: &str) -> Error {
+        Error::FunctionNotFound {
+            name: fn_name.to_owned(),
+            available: self.list_functions().unwrap_or_default(),
+        }
+    }
+
+    /// Finds the artifact with the highest version for the function in the export directory.
+    fn latest_artifact_path(&self, fn_name: &str) -> Result<PathBuf, Error> {
+        let entries = std::fs::read_dir(&self.export_directory)
+            .map_err(Error::Io)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Error::Io)?;
+
+        let latest = entries
+            .into_iter()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let version = artifact_version(fn_name, path.file_name()?.to_str()?)?;
+
+                Some((version, path))
+            })
+            .max_by_key(|(version, _)| *version);
+
+        latest
+            .map(|(_, path)| path)
+            .ok_or_else(|| self.function_not_found(fn_name))
+    }
+
+    /// Parses a textual input value, rejecting unreduced field elements if configured to.
+    fn parse_parameter(&self, name: &str, typ: &AbiType, text: &str) -> Result<InputValue, Error> {
+        if self.reduced_fields {
+            check_reduced(name, typ, text)?;
+        }
+
+        parse_input(name, typ, text)
+    }
+
+    /// Applies the registered input encoders and transforms, validates the inputs and encodes them
+    /// with the ABI.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub(crate) fn encode_inputs(
+        &self,
+        program: &CompiledProgram,
+        mut input_map: BTreeMap<String, InputValue>,
+    ) -> Result<WitnessMap<FieldElement>, Error> {
+        self.encoders.apply(&mut input_map)?;
+
+        self.transforms.apply(&program.abi, &mut input_map);
+
+        fill_absent_options(&program.abi, &mut input_map);
+
+        encode_signed_inputs(&program.abi, &mut input_map);
+
+        validate_input_types(&program.abi, &input_map)?;
+
+        program.abi.encode(&input_map, None).map_err(Error::Abi)
+    }
+
+    pub(crate) fn execute<B: BlackBoxFunctionSolver<FieldElement>>(
+        &self,
+        program: &CompiledProgram,
+        input_map: BTreeMap<String, InputValue>,
+        blackbox_solver: &B,
+    ) -> Result<Option<InputValue>, Error> {
+        let initial_witness = self.encode_inputs(program, input_map)?;
+
+        let Some(results) = &self.results else {
+            return self.solve(program, initial_witness, blackbox_solver);
+        };
+
+        let key = ResultCache::key(program.hash, &initial_witness);
+
+        if let Some(result) = results.get(&key) {
+            return Ok(result);
+        }
+
+        let result = self.solve(program, initial_witness, blackbox_solver)?;
+        results.insert(key, result.clone());
+
+        Ok(result)
+    }
+
+    pub(crate) fn solve<B: BlackBoxFunctionSolver<FieldElement>>(
+        &self,
+        program: &CompiledProgram,
+        initial_witness: WitnessMap<FieldElement>,
+        blackbox_solver: &B,
+    ) -> Result<Option<InputValue>, Error> {
+        let solved_witness_stack =
+            self.solve_witness_stack(program, initial_witness, blackbox_solver)?;
+
+        Self::decode_return(program, &solved_witness_stack)
+    }
+
+    fn solve_witness_stack<B: BlackBoxFunctionSolver<FieldElement>>(
+        &self,
+        program: &CompiledProgram,
+        initial_witness: WitnessMap<FieldElement>,
+        blackbox_solver: &B,
+    ) -> Result<WitnessStack<FieldElement>, Error> {
+        self.solve_witness_stack_with(
+            program,
+            initial_witness,
+            blackbox_solver,
+            &mut DefaultForeignCallBuilder::default().build(),
+        )
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn solve_witness_stack_with<B, E>(
+        &self,
+        program: &CompiledProgram,
+        initial_witness: WitnessMap<FieldElement>,
+        blackbox_solver: &B,
+        foreign_call_executor: &mut E,
+    ) -> Result<WitnessStack<FieldElement>, Error>
+    where
+        B: BlackBoxFunctionSolver<FieldElement>,
+        E: ForeignCallExecutor<FieldElement>,
+    {
+        self.try_solve_witness_stack(
+            program,
+            initial_witness,
+            blackbox_solver,
+            foreign_call_executor,
+            None,
+        )?
+        .map_err(|err| self.diagnose_nargo_error(program, err))
+    }
+
+    /// Executes the program with the given hook, diagnosing nargo's error if it fails.
+    fn solve_witness_stack_with_hook(
+        &self,
+        program: &CompiledProgram,
+        initial_witness: WitnessMap<FieldElement>,
+        hook: &mut dyn ExecutionHook,
+    ) -> Result<WitnessStack<FieldElement>, Error> {
+        self.try_solve_witness_stack(
+            program,
+            initial_witness,
+            &Bn254BlackBoxSolver(false),
+            &mut DefaultForeignCallBuilder::default().build(),
+            Some(hook),
+        )?
+        .map_err(|err| self.diagnose_nargo_error(program, err))
+    }
+
+    /// Executes the program, returning nargo's error as is so the caller can inspect it.
+    ///
+    /// Every run goes through here so that the step limit and panic handling apply to all of them.
+    /// The program is stepped through if a step limit or a hook is given, and executed by nargo
+    /// otherwise.
+    fn try_solve_witness_stack<B, E>(
+        &self,
+        program: &CompiledProgram,
+        initial_witness: WitnessMap<FieldElement>,
+        blackbox_solver: &B,
+        foreign_call_executor: &mut E,
+        hook: Option<&mut dyn ExecutionHook>,
+    ) -> Result<Result<WitnessStack<FieldElement>, NargoError<FieldElement>>, Error>
+    where
+        B: BlackBoxFunctionSolver<FieldElement>,
+        E: ForeignCallExecutor<FieldElement>,
+    {
+        let execute = || match (self.max_brillig_steps, hook) {
+            (None, None) => Ok(execute_program(
+                &program.program,
+                initial_witness,
+                blackbox_solver,
+                foreign_call_executor,
+            )),
+            (max_brillig_steps, hook) => execute_program_with_hook(
+                &program.program,
+                initial_witness,
+                blackbox_solver,
+                foreign_call_executor,
+                &mut (
+                    max_brillig_steps.map(|remaining| StepLimit { remaining }),
+                    hook,
+                ),
+            ),
+        };
+
+        if self.catch_panics {
+            panic::catch_unwind(AssertUnwindSafe(execute)).map_err(|payload| Error::Panic {
+                message: panic_message(payload.as_ref()),
+            })?
+        } else {
+            execute()
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn decode_return(
+        program: &CompiledProgram,
+        solved_witness_stack: &WitnessStack<FieldElement>,
+    ) -> Result<Option<InputValue>, Error> {
+        let result = solved_witness_stack
+            .peek()
+            .map(|witness| &witness.witness)
+            .map(|witness| program.abi.decode(witness).map_err(Error::Abi))
+            .transpose()?
+            .map(|result| result.1)
+            .flatten();
+
+        let result = result.map(|mut result| {
+            if let Some(return_type) = &program.abi.return_type {
+                decode_signed(&return_type.abi_type, &mut result);
+            }
+
+            result
+        });
+
+        Ok(result)
+    }
+
+    fn validate_program(program: &CompiledProgram) -> Result<(), String> {
+        let mut names = BTreeSet::new();
+
+        if let Some(parameter) = program
+            .abi
+            .parameters
+            .iter()
+            .find(|parameter| !names.insert(&parameter.name))
+        {
+            return Err(format!("duplicate parameter {}", parameter.name));
+        }
+
+        let circuit = program
+            .program
+            .functions
+            .first()
+            .ok_or("program has no circuits")?;
+
+        let abi_fields = program.abi.field_count() as usize;
+        let circuit_fields = circuit.private_parameters.len() + circuit.public_parameters.0.len();
+
+        if abi_fields != circuit_fields {
+            return Err(format!(
+                "abi has {abi_fields} parameter fields, circuit has {circuit_fields}"
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn chained_input_map(
+        program: &CompiledProgram,
+        fn_name: &str,
+        output: InputValue,
+    ) -> Result<BTreeMap<String, InputValue>, Error> {
+        match (program.abi.parameters.as_slice(), output) {
+            ([parameter], output) => Ok(BTreeMap::from([(parameter.name.clone(), output)])),
+            (parameters, InputValue::Struct(fields))
+                if parameters.len() == fields.len()
+                    && parameters
+                        .iter()
+                        .all(|parameter| fields.contains_key(&parameter.name)) =>
+            {
+                Ok(fields)
+            }
+            _ => Err(Error::AbiMismatch(format!(
+                "output does not match the parameters of {fn_name}"
+            ))),
+        }
+    }
+
+    /// Maps each parameter to its field elements, parameters occupy consecutive witnesses in ABI
+    /// order.
+    fn input_log(
+        program: &CompiledProgram,
+        initial_witness: &WitnessMap<FieldElement>,
+    ) -> BTreeMap<String, Vec<FieldElement>> {
+        let mut start = 0;
+
+        program
+            .abi
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let end = start + parameter.typ.field_count();
+
+                let fields = (start..end)
+                    .filter_map(|index| initial_witness.get_index(index).copied())
+                    .collect();
+
+                start = end;
+
+                (parameter.name.clone(), fields)
+            })
+            .collect()
+    }
+
+    fn flat_field_array_length(typ: &AbiType) -> Option<u32> {
+        match typ {
+            AbiType::Array { length, typ } if **typ == AbiType::Field => Some(*length),
+            _ => None,
+        }
+    }
+
+    fn is_defaultable(typ: &AbiType) -> bool {
+        match typ {
+            AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => true,
+            AbiType::Array { typ, .. } => Self::is_defaultable(typ),
+            AbiType::Tuple { fields } => fields.iter().all(Self::is_defaultable),
+            AbiType::Struct { fields, .. } => {
+                fields.iter().all(|(_, typ)| Self::is_defaultable(typ))
+            }
+            AbiType::String { .. } => false,
+        }
+    }
+
+    /// Reports the diagnostic of a failed execution to stderr if configured to, and converts the
+    /// error into an [`Error::Execution`] carrying the assertion message and call stack.
+    fn diagnose_nargo_error(
+        &self,
+        program: &CompiledProgram,
+        err: NargoError<FieldElement>,
+    ) -> Error {
+        let diagnostic = self
+            .report_diagnostics
+            .then(|| try_to_diagnose_runtime_error(&err, &program.abi, &program.debug))
+            .flatten();
+
+        if let Some(diagnostic) = diagnostic {
+            diagnostic.report(
+                &DebugArtifact {
+                    debug_symbols: program.debug.clone(),
+                    file_map: program.file_map.clone(),
+                },
+                false,
+            );
+        }
+
+        let call_stack = match &err {
+            NargoError::ExecutionError(
+                ExecutionError::AssertionFailed(_, call_stack, _)
+                | ExecutionError::SolvingError(_, Some(call_stack)),
+            ) => call_stack
+                .iter()
+                .map(|location| {
+                    format!(
+                        "{}:{}",
+                        location.acir_function_index, location.opcode_location
+                    )
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Error::Execution {
+            message: format!("{err:?}"),
+            assertion: err.user_defined_failure_message(&program.abi.error_types),
+            call_stack,
+        }
+    }
+}
+
+impl Clone for NoirRunner {
+    /// Clones the runner configuration, the clone starts with empty artifact and result caches.
+    fn clone(&self) -> Self {
+        Self {
+            program_dir: self.program_dir.clone(),
+            export_directory: self.export_directory.clone(),
+            artifacts: self.artifacts.clone(),
+            cache: Mutex::new(LruCache::new(self.cache().capacity())),
+            encoders: self.encoders.clone(),
+            results: self
+                .results
+                .as_ref()
+                .map(|results| ResultCache::new(results.capacity())),
+            catch_panics: self.catch_panics,
+            reduced_fields: self.reduced_fields,
+            latest_version: self.latest_version,
+            transforms: self.transforms.clone(),
+            numbers: self.numbers.clone(),
+            max_brillig_steps: self.max_brillig_steps,
+            report_diagnostics: self.report_diagnostics,
+            prover_binary: self.prover_binary.clone(),
+            from_manifest: self.from_manifest,
+        }
+    }
+}
+
+/// The nargo version of an artifact, deserialized without the rest of the program.
+#[derive(Deserialize)]
+struct ArtifactVersion {
+    noir_version: String,
+}
+
+/// Deserializes a program from the JSON contents of its artifact, checking its nargo version.
+///
+/// If the program fails to deserialize, a version mismatch is reported instead when the version
+/// alone can be read, as it explains the failure better.
+fn parse_program(bytes: &[u8]) -> Result<CompiledProgram, Error> {
+    let artifact = match serde_json::from_slice::<ProgramArtifact>(bytes) {
+        Ok(artifact) => artifact,
+        Err(err) => {
+            if let Ok(ArtifactVersion { noir_version }) = serde_json::from_slice(bytes) {
+                check_artifact_version(&noir_version)?;
+            }
+
+            return Err(Error::Serde(err));
+        }
+    };
+
+    check_artifact_version(&artifact.noir_version)?;
+
+    Ok(artifact.into())
+}
+
+/// Checks that an artifact was compiled with the nargo version the runner supports.
+///
+/// Build metadata, the commit hash after `+`, is ignored.
+fn check_artifact_version(noir_version: &str) -> Result<(), Error> {
+    let expected = release(NOIR_ARTIFACT_VERSION_STRING).to_owned();
+    let found = release(noir_version).to_owned();
+
+    if expected != found {
+        return Err(Error::VersionMismatch { expected, found });
+    }
+
+    Ok(())
+}
+
+/// Strips the version of a versioned artifact's file stem, so `{fn_name}.v{N}` becomes `fn_name`.
+fn unversioned_name(stem: &str) -> &str {
+    match stem.rsplit_once(".v") {
+        Some((name, version)) if version.parse::<u64>().is_ok() => name,
+        _ => stem,
+    }
+}
+
+/// Returns the release of a nargo version, such as `1.0.0-beta.3`, without its build metadata.
+pub(crate) fn release(version: &str) -> &str {
+    version.split('+').next().unwrap_or_default()
+}
+
+/// Parses the version of an artifact file name for the function.
+///
+/// `{fn_name}.json` is unversioned and has version `0`, `{fn_name}.v{N}.json` has version `N`. Any
+/// other file name returns `None`.
+fn artifact_version(fn_name: &str, file_name: &str) -> Option<u64> {
+    let suffix = file_name.strip_prefix(fn_name)?.strip_suffix(".json")?;
+
+    match suffix {
+        "" => Some(0),
+        suffix => suffix.strip_prefix(".v")?.parse().ok(),
+    }
+}
+
+/// Extracts the message of a panic payload, panics with a formatted message carry a `String`
+/// and panics with a literal message carry a `&str`.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| {
+            payload
+                .downcast_ref::<&str>()
+                .map(|message| (*message).to_owned())
+        })
+        .unwrap_or_else(|| "unknown panic payload".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_version() {
+        assert_eq!(artifact_version("addition", "addition.json"), Some(0));
+        assert_eq!(artifact_version("addition", "addition.v2.json"), Some(2));
+        assert_eq!(artifact_version("addition", "addition.v10.json"), Some(10));
+
+        assert_eq!(artifact_version("addition", "addition_u8.json"), None);
+        assert_eq!(artifact_version("addition", "addition.vx.json"), None);
+        assert_eq!(artifact_version("addition", "addition.v2.toml"), None);
+        assert_eq!(artifact_version("addition", "subtraction.json"), None);
+    }
 
-        err
+    #[test]
+    fn test_unversioned_name() {
+        assert_eq!(unversioned_name("addition"), "addition");
+        assert_eq!(unversioned_name("addition.v2"), "addition");
+        assert_eq!(unversioned_name("addition.vx"), "addition.vx");
     }
 }
@@ -0,0 +1,443 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use crate::option::{PRESENCE_FIELD, VALUE_FIELD};
+use crate::{Error, NumberStrategy};
+
+use acvm::FieldElement;
+use noirc_abi::input_parser::InputValue;
+use serde::ser::{self, Serialize};
+use serde_json::Number;
+
+/// Serializes a value directly into an [`InputValue`].
+///
+/// This follows the JSON data model: integers and booleans become fields, sequences and tuples
+/// become vectors, maps and structs become structs, and strings become strings. Characters become
+/// the field element of their Unicode code point, as a Noir `u8` or `u32` holds a character.
+/// `()` becomes the zero field element. Map keys must serialize as strings.
+///
+/// Integers are converted with the number strategy, except for 128 bit integers beyond the range
+/// of a 64 bit integer, which are converted exactly.
+///
+/// Options become presence-flag structs, `Some(value)` becomes `{ has_value: 1, value }` and
+/// `None` becomes `{ has_value: 0 }`, whose value is zero-filled from the ABI when encoded.
+pub(crate) fn to_input_value<T: Serialize + ?Sized>(value: &T) -> Result<InputValue, Error> {
+    to_input_value_with(value, &NumberStrategy::default())
+}
+
+/// Serializes a value directly into an [`InputValue`], converting numbers with the given strategy.
+pub(crate) fn to_input_value_with<T: Serialize + ?Sized>(
+    value: &T,
+    numbers: &NumberStrategy,
+) -> Result<InputValue, Error> {
+    value
+        .serialize(InputValueSerializer { numbers })
+        .map_err(Error::from)
+}
+
+#[derive(Debug)]
+pub(crate) enum SerializeError {
+    NonFiniteFloat(f64),
+    Strategy(Error),
+    Custom(String),
+}
+
+impl Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFiniteFloat(value) => write!(f, "{value} is not a finite number"),
+            Self::Strategy(err) => write!(f, "{err}"),
+            Self::Custom(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl From<SerializeError> for Error {
+    fn from(err: SerializeError) -> Self {
+        match err {
+            SerializeError::NonFiniteFloat(value) => Error::NonFiniteFloat(value),
+            SerializeError::Strategy(err) => err,
+            SerializeError::Custom(msg) => Error::InvalidValue(msg),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct InputValueSerializer<'a> {
+    numbers: &'a NumberStrategy,
+}
+
+impl<'a> ser::Serializer for InputValueSerializer<'a> {
+    type Ok = InputValue;
+    type Error = SerializeError;
+    type SerializeSeq = SerializeVec<'a>;
+    type SerializeTuple = SerializeVec<'a>;
+    type SerializeTupleStruct = SerializeVec<'a>;
+    type SerializeTupleVariant = SerializeTupleVariant<'a>;
+    type SerializeMap = SerializeMap<'a>;
+    type SerializeStruct = SerializeMap<'a>;
+    type SerializeStructVariant = SerializeStructVariant<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<InputValue, SerializeError> {
+        Ok(InputValue::Field(v.into()))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<InputValue, SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<InputValue, SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<InputValue, SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<InputValue, SerializeError> {
+        self.numbers.convert(&Number::from(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<InputValue, SerializeError> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            // numbers cannot hold 128 bit integers, they are converted exactly instead
+            Err(_) if v < 0 => Ok(InputValue::Field(-FieldElement::from(v.unsigned_abs()))),
+            Err(_) => Ok(InputValue::Field(FieldElement::from(v.unsigned_abs()))),
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<InputValue, SerializeError> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<InputValue, SerializeError> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<InputValue, SerializeError> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<InputValue, SerializeError> {
+        self.numbers.convert(&Number::from(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<InputValue, SerializeError> {
+        match u64::try_from(v) {
+            Ok(v) => self.serialize_u64(v),
+            // numbers cannot hold 128 bit integers, they are converted exactly instead
+            Err(_) => Ok(InputValue::Field(v.into())),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<InputValue, SerializeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<InputValue, SerializeError> {
+        let number = Number::from_f64(v).ok_or(SerializeError::NonFiniteFloat(v))?;
+
+        self.numbers.convert(&number)
+    }
+
+    fn serialize_char(self, v: char) -> Result<InputValue, SerializeError> {
+        Ok(InputValue::Field(u32::from(v).into()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<InputValue, SerializeError> {
+        Ok(InputValue::String(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<InputValue, SerializeError> {
+        let bytes = v
+            .iter()
+            .map(|byte| InputValue::Field(u64::from(*byte).into()));
+
+        Ok(InputValue::Vec(bytes.collect()))
+    }
+
+    fn serialize_none(self) -> Result<InputValue, SerializeError> {
+        Ok(InputValue::Struct(BTreeMap::from([(
+            PRESENCE_FIELD.to_owned(),
+            InputValue::Field(0u32.into()),
+        )])))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(
+        self,
+        value: &T,
+    ) -> Result<InputValue, SerializeError> {
+        Ok(InputValue::Struct(BTreeMap::from([
+            (PRESENCE_FIELD.to_owned(), InputValue::Field(1u32.into())),
+            (VALUE_FIELD.to_owned(), value.serialize(self)?),
+        ])))
+    }
+
+    fn serialize_unit(self) -> Result<InputValue, SerializeError> {
+        Ok(InputValue::Field(0u32.into()))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<InputValue, SerializeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<InputValue, SerializeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<InputValue, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<InputValue, SerializeError> {
+        let value = value.serialize(self)?;
+
+        Ok(InputValue::Struct(BTreeMap::from([(
+            variant.to_owned(),
+            value,
+        )])))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVec<'a>, SerializeError> {
+        Ok(SerializeVec {
+            serializer: self,
+            values: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeVec<'a>, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeVec<'a>, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant<'a>, SerializeError> {
+        Ok(SerializeTupleVariant {
+            serializer: self,
+            variant,
+            values: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap<'a>, SerializeError> {
+        Ok(SerializeMap {
+            serializer: self,
+            fields: BTreeMap::new(),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeMap<'a>, SerializeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeStructVariant<'a>, SerializeError> {
+        Ok(SerializeStructVariant {
+            serializer: self,
+            variant,
+            fields: BTreeMap::new(),
+        })
+    }
+}
+
+struct SerializeVec<'a> {
+    serializer: InputValueSerializer<'a>,
+    values: Vec<InputValue>,
+}
+
+impl ser::SerializeSeq for SerializeVec<'_> {
+    type Ok = InputValue;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.values.push(value.serialize(self.serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<InputValue, SerializeError> {
+        Ok(InputValue::Vec(self.values))
+    }
+}
+
+impl ser::SerializeTuple for SerializeVec<'_> {
+    type Ok = InputValue;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<InputValue, SerializeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVec<'_> {
+    type Ok = InputValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<InputValue, SerializeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct SerializeTupleVariant<'a> {
+    serializer: InputValueSerializer<'a>,
+    variant: &'static str,
+    values: Vec<InputValue>,
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant<'_> {
+    type Ok = InputValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.values.push(value.serialize(self.serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<InputValue, SerializeError> {
+        Ok(InputValue::Struct(BTreeMap::from([(
+            self.variant.to_owned(),
+            InputValue::Vec(self.values),
+        )])))
+    }
+}
+
+struct SerializeMap<'a> {
+    serializer: InputValueSerializer<'a>,
+    fields: BTreeMap<String, InputValue>,
+    next_key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeMap<'_> {
+    type Ok = InputValue;
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializeError> {
+        match key.serialize(self.serializer)? {
+            InputValue::String(key) => {
+                self.next_key = Some(key);
+                Ok(())
+            }
+            _ => Err(ser::Error::custom("key must be a string")),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| ser::Error::custom("serialize_value called before serialize_key"))?;
+
+        self.fields.insert(key, value.serialize(self.serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<InputValue, SerializeError> {
+        Ok(InputValue::Struct(self.fields))
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap<'_> {
+    type Ok = InputValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.fields
+            .insert(key.to_owned(), value.serialize(self.serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<InputValue, SerializeError> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+struct SerializeStructVariant<'a> {
+    serializer: InputValueSerializer<'a>,
+    variant: &'static str,
+    fields: BTreeMap<String, InputValue>,
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant<'_> {
+    type Ok = InputValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.fields
+            .insert(key.to_owned(), value.serialize(self.serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<InputValue, SerializeError> {
+        Ok(InputValue::Struct(BTreeMap::from([(
+            self.variant.to_owned(),
+            InputValue::Struct(self.fields),
+        )])))
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use crate::{Error, NoirRunner};
+
+use bn254_blackbox_solver::Bn254BlackBoxSolver;
+use noirc_abi::input_parser::InputValue;
+use noirc_driver::CompiledProgram;
+
+/// Run Session
+///
+/// Runs a single function repeatedly, reusing the loaded program and black box solver. Use
+/// [`NoirRunner::session`] to open one.
+pub struct RunSession<'a> {
+    runner: &'a NoirRunner,
+    program: Arc<CompiledProgram>,
+    blackbox_solver: Bn254BlackBoxSolver,
+}
+
+impl<'a> RunSession<'a> {
+    pub(crate) fn new(runner: &'a NoirRunner, program: Arc<CompiledProgram>) -> Self {
+        Self {
+            runner,
+            program,
+            blackbox_solver: Bn254BlackBoxSolver(false),
+        }
+    }
+
+    /// Runs the session's function with the given input map.
+    ///
+    /// ## Arguments
+    ///
+    /// - `input_map`: A map of input values to pass to the function.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the output value of the function, if any.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the input values cannot be encoded, the program fails to execute, or
+    /// the output value cannot be decoded.
+    pub fn run(
+        &self,
+        input_map: BTreeMap<String, InputValue>,
+    ) -> Result<Option<InputValue>, Error> {
+        self.runner
+            .execute(&self.program, input_map, &self.blackbox_solver)
+    }
+
+    /// Returns the runner the session was opened from.
+    pub fn runner(&self) -> &NoirRunner {
+        self.runner
+    }
+}
+
+impl Debug for RunSession<'_> {
+    /// Formats the runner and the program's ABI, the solver holds no state worth showing.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RunSession")
+            .field("runner", &self.runner)
+            .field("abi", &self.program.abi)
+            .finish_non_exhaustive()
+    }
+}
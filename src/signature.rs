@@ -0,0 +1,81 @@
+use noirc_abi::{Abi, AbiType, AbiVisibility, Sign};
+
+/// Renders a function's ABI as a Noir-style signature.
+///
+/// Public parameters and return values are prefixed with `pub`, and data bus parameters with
+/// `call_data`. Structs are rendered by name, without their module path.
+pub(crate) fn render_signature(fn_name: &str, abi: &Abi) -> String {
+    let parameters = abi
+        .parameters
+        .iter()
+        .map(|parameter| {
+            format!(
+                "{}: {}{}",
+                parameter.name,
+                render_visibility(&parameter.visibility),
+                render_type(&parameter.typ)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match &abi.return_type {
+        Some(return_type) => format!(
+            "{fn_name}({parameters}) -> {}{}",
+            render_visibility(&return_type.visibility),
+            render_type(&return_type.abi_type)
+        ),
+        None => format!("{fn_name}({parameters})"),
+    }
+}
+
+/// Renders an ABI type as it is written in Noir.
+pub(crate) fn render_type(typ: &AbiType) -> String {
+    match typ {
+        AbiType::Field => "Field".to_owned(),
+        AbiType::Boolean => "bool".to_owned(),
+        AbiType::Integer {
+            sign: Sign::Unsigned,
+            width,
+        } => format!("u{width}"),
+        AbiType::Integer {
+            sign: Sign::Signed,
+            width,
+        } => format!("i{width}"),
+        AbiType::Array { length, typ } => format!("[{}; {length}]", render_type(typ)),
+        AbiType::String { length } => format!("str<{length}>"),
+        // a one-element tuple needs a trailing comma to not read as a parenthesized type
+        AbiType::Tuple { fields } if fields.len() == 1 => format!("({},)", render_type(&fields[0])),
+        AbiType::Tuple { fields } => {
+            let fields = fields.iter().map(render_type).collect::<Vec<_>>();
+
+            format!("({})", fields.join(", "))
+        }
+        AbiType::Struct { path, .. } => path.rsplit("::").next().unwrap_or(path).to_owned(),
+    }
+}
+
+fn render_visibility(visibility: &AbiVisibility) -> &'static str {
+    match visibility {
+        AbiVisibility::Public => "pub ",
+        AbiVisibility::Private => "",
+        AbiVisibility::DataBus => "call_data ",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_tuple() {
+        let tuple = |fields| AbiType::Tuple { fields };
+
+        assert_eq!(render_type(&tuple(vec![AbiType::Field])), "(Field,)");
+        assert_eq!(
+            render_type(&tuple(vec![AbiType::Field, AbiType::Boolean])),
+            "(Field, bool)"
+        );
+        assert_eq!(render_type(&tuple(vec![])), "()");
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use acvm::{AcirField, FieldElement};
+use noirc_abi::input_parser::InputValue;
+use noirc_abi::{Abi, AbiType, Sign};
+
+/// Converts every negative signed integer input into the two's complement form of its ABI type.
+///
+/// [`ToNoir`](crate::ToNoir) has no notion of bit width, so a negative integer `-n` converts to
+/// the field element `p - n`. The ABI encodes signed integers in two's complement of their
+/// declared width instead, so `-5` as an `i8` is `251`. Inputs already in two's complement, such
+/// as those parsed from text, are left as is.
+pub(crate) fn encode_signed_inputs(abi: &Abi, input_map: &mut BTreeMap<String, InputValue>) {
+    for parameter in &abi.parameters {
+        if let Some(value) = input_map.get_mut(&parameter.name) {
+            map_signed(&parameter.typ, value, &to_twos_complement);
+        }
+    }
+}
+
+/// Converts every decoded signed integer input from two's complement back into the form written
+/// by [`ToNoir`](crate::ToNoir), refer to [`decode_signed`].
+pub(crate) fn decode_signed_inputs(abi: &Abi, input_map: &mut BTreeMap<String, InputValue>) {
+    for parameter in &abi.parameters {
+        if let Some(value) = input_map.get_mut(&parameter.name) {
+            decode_signed(&parameter.typ, value);
+        }
+    }
+}
+
+/// Converts every decoded signed integer from two's complement back into the form written by
+/// [`ToNoir`](crate::ToNoir), so a negative output `-n` equals `(-n).to_noir()`.
+pub(crate) fn decode_signed(typ: &AbiType, value: &mut InputValue) {
+    map_signed(typ, value, &from_twos_complement);
+}
+
+fn map_signed(
+    typ: &AbiType,
+    value: &mut InputValue,
+    convert: &impl Fn(u32, FieldElement) -> FieldElement,
+) {
+    match (typ, value) {
+        (
+            AbiType::Integer {
+                sign: Sign::Signed,
+                width,
+            },
+            InputValue::Field(field),
+        ) => {
+            *field = convert(*width, *field);
+        }
+        (AbiType::Array { typ, .. }, InputValue::Vec(values)) => {
+            values
+                .iter_mut()
+                .for_each(|value| map_signed(typ, value, convert));
+        }
+        (AbiType::Tuple { fields }, InputValue::Vec(values)) => {
+            fields
+                .iter()
+                .zip(values)
+                .for_each(|(typ, value)| map_signed(typ, value, convert));
+        }
+        (AbiType::Struct { fields, .. }, InputValue::Struct(values)) => {
+            for (name, typ) in fields {
+                if let Some(value) = values.get_mut(name) {
+                    map_signed(typ, value, convert);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn to_twos_complement(width: u32, field: FieldElement) -> FieldElement {
+    if width >= 128 || field.num_bits() <= width {
+        return field;
+    }
+
+    let magnitude = -field;
+
+    if magnitude.num_bits() > width || magnitude.to_u128() > 1 << (width - 1) {
+        // out of range, left for the ABI encoder to reject
+        return field;
+    }
+
+    FieldElement::from((1u128 << width) - magnitude.to_u128())
+}
+
+fn from_twos_complement(width: u32, field: FieldElement) -> FieldElement {
+    if width == 0 || width >= 128 || field.num_bits() != width {
+        return field;
+    }
+
+    -FieldElement::from((1u128 << width) - field.to_u128())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn i8_type() -> AbiType {
+        AbiType::Integer {
+            sign: Sign::Signed,
+            width: 8,
+        }
+    }
+
+    #[test]
+    fn test_encode_negative() {
+        let mut value = InputValue::Field(-FieldElement::from(5u32));
+
+        map_signed(&i8_type(), &mut value, &to_twos_complement);
+
+        assert_eq!(value, InputValue::Field(251u32.into()));
+
+        decode_signed(&i8_type(), &mut value);
+
+        assert_eq!(value, InputValue::Field(-FieldElement::from(5u32)));
+    }
+
+    #[test]
+    fn test_positive_unchanged() {
+        let mut value = InputValue::Field(127u32.into());
+
+        map_signed(&i8_type(), &mut value, &to_twos_complement);
+        decode_signed(&i8_type(), &mut value);
+
+        assert_eq!(value, InputValue::Field(127u32.into()));
+    }
+
+    #[test]
+    fn test_out_of_range_unchanged() {
+        let field = -FieldElement::from(129u32);
+        let mut value = InputValue::Field(field);
+
+        map_signed(&i8_type(), &mut value, &to_twos_complement);
+
+        assert_eq!(value, InputValue::Field(field));
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use crate::Error;
+
+use noirc_abi::input_parser::InputValue;
+use noirc_abi::{Abi, AbiType};
+
+/// Validates that each input has the [`InputValue`] variant expected by its ABI type, and that
+/// arrays, tuples and strings have their declared length.
+///
+/// Strings are measured in bytes, as a Noir `str<N>` holds `N` bytes.
+///
+/// Parameters missing from the input map are skipped, they are reported when the inputs are
+/// encoded.
+pub(crate) fn validate_input_types(
+    abi: &Abi,
+    input_map: &BTreeMap<String, InputValue>,
+) -> Result<(), Error> {
+    abi.parameters
+        .iter()
+        .try_for_each(|parameter| match input_map.get(&parameter.name) {
+            Some(value) => validate_type(&parameter.name, &parameter.typ, value),
+            None => Ok(()),
+        })
+}
+
+fn validate_type(path: &str, typ: &AbiType, value: &InputValue) -> Result<(), Error> {
+    match (typ, value) {
+        (AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean, InputValue::Field(_)) => {
+            Ok(())
+        }
+        (AbiType::Array { length, typ }, InputValue::Vec(values)) => {
+            check_length(path, *length as usize, values.len())?;
+
+            values.iter().enumerate().try_for_each(|(index, value)| {
+                validate_type(&format!("{path}[{index}]"), typ, value)
+            })
+        }
+        (AbiType::Tuple { fields }, InputValue::Vec(values)) => {
+            check_length(path, fields.len(), values.len())?;
+
+            fields
+                .iter()
+                .zip(values)
+                .enumerate()
+                .try_for_each(|(index, (typ, value))| {
+                    validate_type(&format!("{path}.{index}"), typ, value)
+                })
+        }
+        (AbiType::Struct { fields, .. }, InputValue::Struct(values)) => {
+            fields
+                .iter()
+                .try_for_each(|(name, typ)| match values.get(name) {
+                    Some(value) => validate_type(&format!("{path}.{name}"), typ, value),
+                    None => Ok(()),
+                })
+        }
+        (AbiType::String { length }, InputValue::String(string)) => {
+            check_length(path, *length as usize, string.len())
+        }
+        (typ, value) => Err(Error::TypeMismatch {
+            param: path.to_owned(),
+            expected: expected_variant(typ).to_owned(),
+            found: variant_name(value).to_owned(),
+        }),
+    }
+}
+
+fn check_length(path: &str, expected: usize, found: usize) -> Result<(), Error> {
+    if expected != found {
+        return Err(Error::LengthMismatch {
+            param: path.to_owned(),
+            expected,
+            found,
+        });
+    }
+
+    Ok(())
+}
+
+fn expected_variant(typ: &AbiType) -> &'static str {
+    match typ {
+        AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => "Field",
+        AbiType::Array { .. } | AbiType::Tuple { .. } => "Vec",
+        AbiType::Struct { .. } => "Struct",
+        AbiType::String { .. } => "String",
+    }
+}
+
+pub(crate) fn variant_name(value: &InputValue) -> &'static str {
+    match value {
+        InputValue::Field(_) => "Field",
+        InputValue::String(_) => "String",
+        InputValue::Vec(_) => "Vec",
+        InputValue::Struct(_) => "Struct",
+    }
+}
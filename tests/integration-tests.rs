@@ -1,7 +1,12 @@
-use noir_runner::{FieldElement, InputValue, NoirRunner, ToNoir};
+use noir_runner::{
+    AbiType, Error, FieldElement, FromNoir, InputValue, NoirEq, NoirRunner, NumberStrategy,
+    RunOutcome, ToNoir, WitnessStack, from_input_value, merkle_path_to_fields, noir_struct,
+};
+use serde::Serialize;
 
 use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 #[test]
 fn test_noir_runner() {
@@ -39,3 +44,2241 @@ fn test_noir_runner_with_abi() {
 
     assert_eq!(result, expected.to_noir());
 }
+
+#[test]
+fn test_noir_runner_with_resource_stats() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let (result, stats) = runner
+        .run_with_resource_stats("addition", input_map)
+        .unwrap();
+
+    assert_eq!(result.unwrap(), 5i128.to_noir());
+    assert_eq!(stats.peak_witness_count, 3);
+    assert_eq!(stats.peak_brillig_memory_slots, 0);
+    assert_eq!(stats.brillig_steps, 0);
+}
+
+#[test]
+fn test_noir_runner_metrics() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let metrics = runner.metrics("addition").unwrap();
+
+    assert!(metrics.acir_opcodes > 0);
+    assert_eq!(metrics.brillig_opcodes, 0);
+    assert_eq!(metrics.public_inputs, 0);
+    assert_eq!(metrics.private_inputs, 2);
+    assert!(metrics.witness_count >= 3);
+
+    assert!(runner.metrics("count_up").unwrap().brillig_opcodes > 0);
+}
+
+#[test]
+fn test_noir_runner_run_fields() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let inputs = [1u128, 2, 3].map(FieldElement::from);
+
+    let result = runner.run_fields("double_each", &inputs).unwrap();
+
+    assert_eq!(result, [2u128, 4, 6].map(FieldElement::from).to_vec());
+}
+
+#[test]
+fn test_noir_runner_run_fields_abi_mismatch() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let result = runner.run_fields("addition", &[FieldElement::from(1u128)]);
+
+    assert!(matches!(result, Err(Error::AbiMismatch(_))));
+}
+
+#[test]
+fn test_noir_runner_list_functions() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let functions = runner.list_functions().unwrap();
+
+    assert!(functions.contains(&"addition".to_owned()));
+}
+
+#[cfg(feature = "include_dir")]
+#[test]
+fn test_noir_runner_from_include_dir() {
+    static EXPORT_DIR: include_dir::Dir =
+        include_dir::include_dir!("$CARGO_MANIFEST_DIR/tests/export");
+
+    let runner = NoirRunner::from_include_dir(&EXPORT_DIR);
+
+    assert!(
+        runner
+            .list_functions()
+            .unwrap()
+            .contains(&"addition".to_owned())
+    );
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let result = runner.run("addition", input_map).unwrap().unwrap();
+
+    assert_eq!(result, 5i128.to_noir());
+}
+
+#[test]
+fn test_noir_runner_run_chained() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 2i128.to_noir())]);
+
+    let pair = runner.run("make_pair", input_map).unwrap().unwrap();
+
+    let witness = runner.encode_chained("sum_pair", pair.clone()).unwrap();
+
+    assert_eq!(witness.get_index(0), Some(&FieldElement::from(2i128)));
+    assert_eq!(witness.get_index(1), Some(&FieldElement::from(3i128)));
+
+    let result = runner.run_chained("sum_pair", pair).unwrap().unwrap();
+
+    assert_eq!(result, 5i128.to_noir());
+}
+
+#[test]
+fn test_noir_runner_type_mismatch_vec_for_field() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), vec![2i128].to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let result = runner.run("addition", input_map);
+
+    assert!(matches!(
+        result,
+        Err(Error::TypeMismatch { param, expected, found })
+            if param == "x" && expected == "Field" && found == "Vec"
+    ));
+}
+
+#[test]
+fn test_noir_runner_type_mismatch_struct_for_array() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), noir_struct! { a: 1i128.to_noir() })]);
+
+    let result = runner.run("double_each", input_map);
+
+    assert!(matches!(
+        result,
+        Err(Error::TypeMismatch { param, expected, found })
+            if param == "x" && expected == "Vec" && found == "Struct"
+    ));
+}
+
+#[test]
+fn test_noir_runner_length_mismatch() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), vec![1u64, 2].to_noir())]);
+
+    let result = runner.run("double_each", input_map);
+
+    assert!(matches!(
+        result,
+        Err(Error::LengthMismatch { param, expected: 3, found: 2 }) if param == "x"
+    ));
+
+    let input_map = BTreeMap::from([("x".to_owned(), vec![1u64, 2, 3].to_noir())]);
+
+    assert!(runner.run("double_each", input_map).is_ok());
+}
+
+#[test]
+fn test_noir_runner_run_typed_bool() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let equal = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 2i128.to_noir()),
+    ]);
+    let unequal = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert_eq!(
+        runner.run_typed::<bool>("is_equal", equal).unwrap(),
+        Some(true)
+    );
+    assert_eq!(
+        runner.run_typed::<bool>("is_equal", unequal).unwrap(),
+        Some(false)
+    );
+}
+
+#[test]
+fn test_noir_runner_builder_max_cached_artifacts() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .max_cached_artifacts(1)
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert_eq!(
+        runner.run("addition", input_map.clone()).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+    assert_eq!(
+        runner
+            .run_typed::<bool>("is_equal", input_map.clone())
+            .unwrap(),
+        Some(false)
+    );
+    assert_eq!(
+        runner.run("addition", input_map).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+}
+
+#[test]
+fn test_noir_runner_black_box_functions() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    assert!(runner.black_box_functions("addition").unwrap().is_empty());
+    assert!(
+        runner
+            .black_box_functions("blake3_hash")
+            .unwrap()
+            .contains(&"blake3".to_owned())
+    );
+}
+
+#[test]
+fn test_noir_runner_session() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let session = runner.session("addition").unwrap();
+
+    for x in 0..4i128 {
+        let input_map = BTreeMap::from([
+            ("x".to_owned(), x.to_noir()),
+            ("y".to_owned(), 1i128.to_noir()),
+        ]);
+
+        assert_eq!(session.run(input_map).unwrap().unwrap(), (x + 1).to_noir());
+    }
+}
+
+#[test]
+fn test_noir_runner_run_from_kv() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let result = runner
+        .run_from_kv("addition", &["x=2", "y=0x03"])
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result, 5i128.to_noir());
+}
+
+#[test]
+fn test_noir_runner_run_from_kv_integer() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let result = runner
+        .run_from_kv("add_u8", &["x=200", "y=55"])
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result, 255u8.to_noir());
+
+    let result = runner.run_from_kv("add_u8", &["x=256", "y=0"]);
+
+    assert!(matches!(result, Err(Error::InvalidInput { param, .. }) if param == "x"));
+}
+
+#[test]
+fn test_noir_runner_run_from_kv_unknown_parameter() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let result = runner.run_from_kv("addition", &["x=2", "z=3"]);
+
+    assert!(matches!(result, Err(Error::UnknownParameter(param)) if param == "z"));
+}
+
+#[test]
+fn test_noir_runner_abi_json() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let abi_json = runner.abi_json("addition").unwrap();
+
+    let abi: serde_json::Value = serde_json::from_str(&abi_json).unwrap();
+
+    assert_eq!(abi["parameters"][0]["name"], "x");
+    assert_eq!(abi["parameters"][1]["name"], "y");
+    assert_eq!(abi["parameters"][0]["type"]["kind"], "field");
+    assert_eq!(abi["return_type"]["abi_type"]["kind"], "field");
+}
+
+#[test]
+fn test_noir_runner_run_until() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let result = runner
+        .run_until("addition", input_map.clone(), deadline)
+        .unwrap();
+
+    assert_eq!(result.unwrap(), 5i128.to_noir());
+
+    let deadline = Instant::now() - Duration::from_secs(1);
+    let result = runner.run_until("addition", input_map, deadline);
+
+    assert!(matches!(result, Err(Error::Timeout)));
+}
+
+#[test]
+fn test_noir_runner_run_with_timeout() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let result = runner.run_with_timeout("addition", input_map.clone(), Duration::from_secs(60));
+
+    assert_eq!(result.unwrap().unwrap(), 5i128.to_noir());
+
+    let result = runner.run_with_timeout("addition", input_map.clone(), Duration::ZERO);
+
+    assert!(matches!(result, Err(Error::Timeout)));
+
+    let result = runner.run_with_timeout("addition", input_map, Duration::MAX);
+
+    assert_eq!(result.unwrap().unwrap(), 5i128.to_noir());
+}
+
+#[cfg(feature = "conformance")]
+#[test]
+fn test_noir_runner_conformance() {
+    use noir_runner::compare_with_nargo;
+
+    if std::process::Command::new("nargo")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        eprintln!("skipping conformance test, nargo is not installed");
+        return;
+    }
+
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let package_dir = PathBuf::from("tests/conformance/addition");
+    let report = compare_with_nargo(&runner, "addition", input_map, &package_dir).unwrap();
+
+    assert!(report.is_conformant(), "{report:?}");
+
+    let package_dir = PathBuf::from("tests/conformance/negate");
+
+    for (x, negated) in [(5i64, -5i64), (-5, 5)] {
+        let input_map = BTreeMap::from([("x".to_owned(), x.to_noir())]);
+        let report = compare_with_nargo(&runner, "negate", input_map, &package_dir).unwrap();
+
+        assert!(report.is_conformant(), "{report:?}");
+        assert_eq!(report.nargo_output, Some(negated.to_noir()));
+    }
+}
+
+#[test]
+fn test_noir_runner_builder_encoder() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .encoder("x", |value| match value {
+            InputValue::String(hex) => Ok(u64::from_str_radix(&hex, 16).unwrap().to_noir()),
+            value => Ok(value),
+        })
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), "ff".to_noir()),
+        ("y".to_owned(), 1i128.to_noir()),
+    ]);
+
+    let result = runner.run("addition", input_map).unwrap().unwrap();
+
+    assert_eq!(result, 256i128.to_noir());
+}
+
+#[test]
+fn test_noir_runner_validate_all() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let report = runner.validate_all().unwrap();
+
+    assert!(report.results.contains_key("addition"));
+    assert!(
+        report.is_ok(),
+        "{:?}",
+        report.failures().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_noir_runner_run_typed_return_type_mismatch() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 2i128.to_noir())]);
+
+    let result = runner.run_typed::<bool>("make_pair", input_map);
+
+    assert!(matches!(
+        result,
+        Err(Error::ReturnTypeMismatch { expected, found })
+            if expected == "Field" && found == "Struct"
+    ));
+}
+
+#[test]
+fn test_noir_runner_signature() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    assert_eq!(
+        runner.signature("addition").unwrap(),
+        "addition(x: Field, y: Field) -> Field"
+    );
+    assert_eq!(
+        runner.signature("sum_pair").unwrap(),
+        "sum_pair(pair: Pair) -> Field"
+    );
+    assert_eq!(
+        runner.signature("double_each").unwrap(),
+        "double_each(x: [Field; 3]) -> [Field; 3]"
+    );
+    assert_eq!(
+        runner.signature("unwrap_single").unwrap(),
+        "unwrap_single(single: (Field,)) -> Field"
+    );
+}
+
+#[test]
+fn test_noir_runner_return_witness_count() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    assert_eq!(runner.return_witness_count("addition").unwrap(), 1);
+    assert_eq!(runner.return_witness_count("double_each").unwrap(), 3);
+}
+
+#[test]
+fn test_noir_runner_memoize_results() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .memoize_results(8)
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert_eq!(
+        runner.run("addition", input_map.clone()).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+    assert_eq!(runner.result_cache_hits(), 0);
+
+    assert_eq!(
+        runner.run("addition", input_map).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+    assert_eq!(runner.result_cache_hits(), 1);
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Inner {
+    a: Option<u32>,
+}
+
+impl FromNoir for Inner {
+    fn from_noir(value: InputValue) -> Result<Self, Error> {
+        let InputValue::Struct(mut fields) = value else {
+            return Err(Error::InvalidOutput("expected a struct".to_owned()));
+        };
+
+        let a = fields
+            .remove("a")
+            .ok_or_else(|| Error::InvalidOutput("missing a".to_owned()))?;
+
+        Ok(Self {
+            a: Option::from_noir(a)?,
+        })
+    }
+}
+
+#[test]
+fn test_noir_runner_nested_option() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let cases = [
+        (Some(Inner { a: Some(7) }), Some(Inner { a: Some(8) })),
+        (Some(Inner { a: None }), Some(Inner { a: None })),
+        (None, None),
+    ];
+
+    for (input, expected) in cases {
+        let input_map = BTreeMap::from([("x".to_owned(), input.to_noir())]);
+
+        let result = runner
+            .run_typed::<Option<Inner>>("increment_optional", input_map)
+            .unwrap();
+
+        assert_eq!(result, Some(expected));
+    }
+
+    let input_map = BTreeMap::from([("x".to_owned(), None::<Inner>.to_noir())]);
+
+    let output = runner
+        .run("increment_optional", input_map)
+        .unwrap()
+        .unwrap();
+
+    assert!(output.equals(None::<Inner>));
+}
+
+#[test]
+fn test_noir_runner_serde_rename() {
+    #[derive(Serialize)]
+    struct Pair {
+        #[serde(rename = "a")]
+        left: u32,
+        #[serde(rename = "b")]
+        right: u32,
+    }
+
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("pair".to_owned(), Pair { left: 2, right: 3 }.to_noir())]);
+
+    let result = runner.run("sum_pair", input_map).unwrap().unwrap();
+
+    assert_eq!(result, 5u32.to_noir());
+}
+
+#[test]
+fn test_noir_runner_run_to_witness_bytes() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let bytes = runner.run_to_witness_bytes("addition", input_map).unwrap();
+
+    // the witness stack is gzip compressed
+    assert_eq!(bytes[..2], [0x1f, 0x8b]);
+}
+
+#[test]
+fn test_noir_runner_run_and_save_witness() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let out_path =
+        std::env::temp_dir().join(format!("noir-runner-witness-{}.gz", std::process::id()));
+
+    let result = runner
+        .run_and_save_witness("addition", input_map.clone(), &out_path)
+        .unwrap();
+
+    assert_eq!(result, Some(5i128.to_noir()));
+
+    let bytes = std::fs::read(&out_path).unwrap();
+    std::fs::remove_file(&out_path).unwrap();
+
+    assert_eq!(
+        bytes,
+        runner
+            .run_to_witness_bytes("addition", input_map.clone())
+            .unwrap()
+    );
+
+    let (_, witness_stack) = runner.run_with_witness("addition", input_map).unwrap();
+
+    assert_eq!(
+        WitnessStack::try_from(bytes.as_slice()).unwrap(),
+        witness_stack
+    );
+}
+
+#[test]
+fn test_noir_runner_builder_catch_panics() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .catch_panics(true)
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert_eq!(
+        runner.run("addition", input_map).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+}
+
+#[test]
+fn test_noir_runner_run_streaming() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), vec![1u64, 2, 3].to_noir())]);
+
+    let result = runner
+        .run_streaming("double_each", input_map)
+        .unwrap()
+        .collect::<Vec<_>>();
+
+    assert_eq!(result, [2u128, 4, 6].map(FieldElement::from));
+
+    let input_map = BTreeMap::from([("x".to_owned(), 2i128.to_noir())]);
+
+    assert!(matches!(
+        runner.run_streaming("make_pair", input_map),
+        Err(Error::AbiMismatch(_))
+    ));
+}
+
+#[test]
+fn test_noir_runner_builder_reduced_fields() {
+    // the BN254 scalar field modulus
+    let modulus = "x=0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir.clone()).unwrap();
+
+    let result = runner
+        .run_from_kv("addition", &[modulus, "y=3"])
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result, 3i128.to_noir());
+
+    let runner = NoirRunner::builder(program_dir)
+        .reduced_fields(true)
+        .build()
+        .unwrap();
+
+    let result = runner.run_from_kv("addition", &[modulus, "y=3"]);
+
+    assert!(matches!(result, Err(Error::InvalidInput { param, .. }) if param == "x"));
+}
+
+#[test]
+fn test_noir_runner_try_run() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 1u8.to_noir()),
+        ("y".to_owned(), 2u8.to_noir()),
+    ]);
+
+    let outcome = runner.try_run("add_u8", input_map).unwrap();
+
+    assert_eq!(outcome, RunOutcome::Satisfied(Some(3u8.to_noir())));
+
+    // the sum overflows a u8
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 200u8.to_noir()),
+        ("y".to_owned(), 100u8.to_noir()),
+    ]);
+
+    let outcome = runner.try_run("add_u8", input_map).unwrap();
+
+    assert!(matches!(outcome, RunOutcome::Unsatisfied(_)));
+}
+
+#[test]
+fn test_noir_runner_builder_latest_version() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .latest_version(true)
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert_eq!(
+        runner.run("addition", input_map.clone()).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+    assert!(matches!(
+        runner.run("subtraction", input_map),
+        Err(Error::FunctionNotFound { name, .. }) if name == "subtraction"
+    ));
+
+    let program_dir = std::env::temp_dir().join("noir_runner_latest_version");
+    let export_dir = program_dir.join("export");
+
+    std::fs::create_dir_all(program_dir.join("src")).unwrap();
+    std::fs::write(
+        program_dir.join("Nargo.toml"),
+        "[package]\nname = \"latest\"\ntype = \"lib\"\nauthors = [\"\"]\n\n[dependencies]\n",
+    )
+    .unwrap();
+    std::fs::write(program_dir.join("src/lib.nr"), "").unwrap();
+    std::fs::create_dir_all(&export_dir).unwrap();
+
+    for file_name in ["addition.json", "addition.v2.json", "addition.v10.json"] {
+        std::fs::copy("tests/export/addition.json", export_dir.join(file_name)).unwrap();
+    }
+
+    let runner = NoirRunner::builder(program_dir)
+        .latest_version(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(runner.list_functions().unwrap(), ["addition"]);
+}
+
+#[test]
+fn test_noir_runner_defaultable_parameters() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    assert_eq!(
+        runner.defaultable_parameters("label_sum").unwrap(),
+        BTreeMap::from([("label".to_owned(), false), ("pair".to_owned(), true)])
+    );
+    assert_eq!(
+        runner.defaultable_parameters("double_each").unwrap(),
+        BTreeMap::from([("x".to_owned(), true)])
+    );
+}
+
+#[test]
+fn test_noir_runner_builder_input_transform() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .input_transform(|abi, input_map| {
+            if abi.parameters.iter().any(|parameter| parameter.name == "y") {
+                input_map.entry("y".to_owned()).or_insert(10i128.to_noir());
+            }
+        })
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 2i128.to_noir())]);
+
+    assert_eq!(
+        runner.run("addition", input_map).unwrap().unwrap(),
+        12i128.to_noir()
+    );
+}
+
+#[derive(Debug, PartialEq)]
+struct Pair {
+    a: FieldElement,
+    b: FieldElement,
+}
+
+impl FromNoir for Pair {
+    fn from_noir(value: InputValue) -> Result<Self, Error> {
+        let InputValue::Struct(mut fields) = value else {
+            return Err(Error::InvalidOutput("expected a struct".to_owned()));
+        };
+
+        let mut field = |name: &str| {
+            let value = fields
+                .remove(name)
+                .ok_or_else(|| Error::InvalidOutput(format!("missing {name}")))?;
+
+            FieldElement::from_noir(value)
+        };
+
+        Ok(Self {
+            a: field("a")?,
+            b: field("b")?,
+        })
+    }
+}
+
+#[test]
+fn test_noir_runner_run_typed_array_of_structs() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 1i128.to_noir())]);
+
+    let result = runner
+        .run_typed::<Vec<Pair>>("make_pairs", input_map)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        result,
+        vec![
+            Pair {
+                a: FieldElement::from(1u128),
+                b: FieldElement::from(2u128),
+            },
+            Pair {
+                a: FieldElement::from(3u128),
+                b: FieldElement::from(4u128),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_noir_runner_run_with_input_log() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let (result, input_log) = runner.run_with_input_log("addition", input_map);
+
+    assert_eq!(result.unwrap().unwrap(), 5i128.to_noir());
+    assert_eq!(
+        input_log,
+        BTreeMap::from([
+            ("x".to_owned(), vec![FieldElement::from(2u128)]),
+            ("y".to_owned(), vec![FieldElement::from(3u128)]),
+        ])
+    );
+}
+
+#[test]
+fn test_noir_runner_preload_lenient() {
+    let program_dir = std::env::temp_dir().join("noir_runner_preload_lenient");
+    let export_dir = program_dir.join("export");
+
+    std::fs::create_dir_all(&export_dir).unwrap();
+    std::fs::write(
+        program_dir.join("Nargo.toml"),
+        "[package]\nname = \"preload\"\ntype = \"lib\"\nauthors = [\"\"]\n\n[dependencies]\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(program_dir.join("src")).unwrap();
+    std::fs::write(program_dir.join("src/lib.nr"), "").unwrap();
+    std::fs::copy(
+        "tests/export/addition.json",
+        export_dir.join("addition.json"),
+    )
+    .unwrap();
+    std::fs::write(export_dir.join("corrupt.json"), "{ not json").unwrap();
+
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let report = runner.preload_lenient().unwrap();
+
+    assert_eq!(report.successes().collect::<Vec<_>>(), ["addition"]);
+    let failures = report.failures().collect::<Vec<_>>();
+
+    assert!(matches!(
+        failures.as_slice(),
+        [("corrupt", Error::Serde(_))]
+    ));
+}
+
+#[test]
+fn test_noir_runner_artifact_nargo_version() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let version = runner.artifact_nargo_version("addition").unwrap();
+
+    assert!(version.starts_with("1.0.0-beta.3"), "{version}");
+}
+
+#[test]
+fn test_noir_runner_builder_number_strategy() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .number_strategy(NumberStrategy::ExactInteger)
+        .build()
+        .unwrap();
+
+    let input = serde_json::json!({ "x": 2.0, "y": 3 });
+
+    let input_map = match runner.to_input_value(&input).unwrap() {
+        InputValue::Struct(input_map) => input_map,
+        value => panic!("expected a struct, found {value:?}"),
+    };
+
+    assert_eq!(
+        runner.run("addition", input_map).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+    assert!(matches!(
+        runner.to_input_value(&serde_json::json!(2.5)),
+        Err(Error::InvalidValue(_))
+    ));
+}
+
+#[test]
+fn test_noir_runner_run_keyed() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let results = runner.run_keyed(vec![
+        (7u64, "addition".to_owned(), input_map.clone()),
+        (3u64, "missing".to_owned(), input_map),
+    ]);
+
+    assert!(matches!(
+        results.as_slice(),
+        [(7, Ok(Some(_))), (3, Err(Error::FunctionNotFound { .. }))]
+    ));
+}
+
+#[test]
+fn test_noir_runner_run_batch() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let inputs = (0..3i128)
+        .map(|x| {
+            BTreeMap::from([
+                ("x".to_owned(), x.to_noir()),
+                ("y".to_owned(), 1i128.to_noir()),
+            ])
+        })
+        .collect();
+
+    let results = runner.run_batch("addition", inputs).unwrap();
+
+    assert_eq!(
+        results,
+        vec![
+            Some(1i128.to_noir()),
+            Some(2i128.to_noir()),
+            Some(3i128.to_noir())
+        ]
+    );
+    assert!(runner.run_batch("missing", Vec::new()).is_err());
+
+    let inputs = (0..3i128)
+        .map(|x| BTreeMap::from([("x".to_owned(), x.to_noir())]))
+        .collect();
+
+    let result = runner.run_batch("assert_zero", inputs);
+
+    assert!(matches!(result, Err(Error::Batch { index: 1 | 2, .. })));
+}
+
+#[test]
+fn test_noir_runner_return_type() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    assert_eq!(
+        runner.return_type("addition").unwrap(),
+        Some(AbiType::Field)
+    );
+    assert_eq!(runner.return_type("assert_equal").unwrap(), None);
+}
+
+#[test]
+fn test_noir_runner_run_from_env() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    // SAFETY: the variables are unique to this test and no other thread reads them
+    unsafe {
+        std::env::set_var("RUN_FROM_ENV_X", "2");
+        std::env::set_var("RUN_FROM_ENV_Y", "0x03");
+    }
+
+    let result = runner
+        .run_from_env("addition", "RUN_FROM_ENV_")
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result, 5i128.to_noir());
+
+    let result = runner.run_from_env("addition", "RUN_FROM_ENV_MISSING_");
+
+    assert!(matches!(result, Err(Error::InvalidInput { param, .. }) if param == "x"));
+}
+
+#[test]
+fn test_noir_runner_builder_max_brillig_steps() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .max_brillig_steps(100)
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([("n".to_owned(), 1u32.to_noir())]);
+
+    assert_eq!(
+        runner.run("count_up", input_map).unwrap().unwrap(),
+        1u32.to_noir()
+    );
+
+    let input_map = BTreeMap::from([("n".to_owned(), 1_000u32.to_noir())]);
+
+    assert!(matches!(
+        runner.run("count_up", input_map.clone()),
+        Err(Error::StepLimitExceeded)
+    ));
+
+    assert!(matches!(
+        runner.run_with_resource_stats("count_up", input_map.clone()),
+        Err(Error::StepLimitExceeded)
+    ));
+
+    assert!(matches!(
+        runner.run_with_timeout("count_up", input_map, Duration::from_secs(60)),
+        Err(Error::StepLimitExceeded)
+    ));
+}
+
+#[test]
+fn test_noir_runner_stepped_execution_error() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .max_brillig_steps(100)
+        .report_diagnostics(false)
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 1u64.to_noir())]);
+
+    let err = runner.run("assert_zero", input_map.clone()).unwrap_err();
+
+    assert_eq!(err.assertion_message(), Some("x must be zero"));
+
+    let outcome = runner.try_run("assert_zero", input_map.clone()).unwrap();
+
+    assert!(!outcome.is_satisfied());
+
+    let err = runner
+        .run_with_timeout("assert_zero", input_map.clone(), Duration::from_secs(60))
+        .unwrap_err();
+
+    assert!(matches!(err, Error::Execution { call_stack, .. } if !call_stack.is_empty()));
+
+    let err = runner
+        .run_with_resource_stats("assert_zero", input_map)
+        .unwrap_err();
+
+    assert_eq!(err.assertion_message(), Some("x must be zero"));
+
+    let input_map = BTreeMap::from([("values".to_owned(), [1u32, 0, 3, 4].to_noir())]);
+
+    let err = runner.run("brillig_sum", input_map).unwrap_err();
+
+    assert_eq!(err.assertion_message(), Some("values must be non-zero"));
+}
+
+#[test]
+fn test_noir_runner_output_equals() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let output = runner.run("addition", input_map).unwrap().unwrap();
+
+    assert!(output.equals(5u64));
+    assert!(!output.equals(6u64));
+}
+
+#[test]
+fn test_noir_runner_dry_run() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    // the inputs encode, even though the assertion would fail
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert!(runner.dry_run("assert_equal", input_map).is_ok());
+
+    let input_map = BTreeMap::from([("x".to_owned(), 2i128.to_noir())]);
+
+    assert!(matches!(
+        runner.dry_run("assert_equal", input_map),
+        Err(Error::Abi(_))
+    ));
+}
+
+#[test]
+fn test_noir_runner_check_inputs() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let initial_witness = runner.check_inputs("addition", input_map.clone()).unwrap();
+
+    assert_eq!(
+        initial_witness,
+        runner
+            .get_abi("addition")
+            .unwrap()
+            .encode(&input_map, None)
+            .unwrap()
+    );
+
+    let result = runner
+        .run_with_initial_witness("addition", initial_witness)
+        .unwrap();
+
+    assert_eq!(result, Some(5i128.to_noir()));
+
+    let input_map = BTreeMap::from([("x".to_owned(), vec![1u64, 2].to_noir())]);
+
+    assert!(matches!(
+        runner.check_inputs("addition", input_map),
+        Err(Error::TypeMismatch { param, .. }) if param == "x"
+    ));
+}
+
+#[test]
+fn test_noir_runner_merkle_path() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let path = (1u8..=4)
+        .map(|sibling| {
+            let mut hash = [0u8; 32];
+            hash[31] = sibling;
+            hash
+        })
+        .collect::<Vec<_>>();
+
+    let input_map = BTreeMap::from([("path".to_owned(), merkle_path_to_fields(&path))]);
+
+    let result = runner.run("merkle_path_sum", input_map).unwrap().unwrap();
+
+    assert_eq!(result, 10u32.to_noir());
+}
+
+#[test]
+fn test_noir_runner_pin() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .max_cached_artifacts(1)
+        .build()
+        .unwrap();
+
+    runner.pin("addition").unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert_eq!(
+        runner
+            .run_typed::<bool>("is_equal", input_map.clone())
+            .unwrap(),
+        Some(false)
+    );
+    assert_eq!(
+        runner.run("addition", input_map).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+
+    runner.unpin("addition");
+
+    assert!(runner.pin("missing").is_err());
+}
+
+#[test]
+fn test_noir_runner_clear_cache() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir.clone()).unwrap();
+    let uncached = NoirRunner::builder(program_dir)
+        .max_cached_artifacts(0)
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    runner.pin("addition").unwrap();
+    runner.clear_cache();
+
+    assert_eq!(
+        runner.run("addition", input_map.clone()).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+    assert_eq!(
+        uncached
+            .run("addition", input_map.clone())
+            .unwrap()
+            .unwrap(),
+        5i128.to_noir()
+    );
+    assert_eq!(
+        uncached.run("addition", input_map).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+}
+
+#[test]
+fn test_noir_runner_run_echo() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let (inputs, result) = runner.run_echo("addition", input_map.clone()).unwrap();
+
+    assert_eq!(inputs, input_map);
+    assert_eq!(result.unwrap(), 5i128.to_noir());
+}
+
+#[test]
+fn test_noir_runner_list_functions_ignores_other_entries() {
+    let program_dir = std::env::temp_dir().join("noir_runner_list_functions");
+    let export_dir = program_dir.join("export");
+
+    std::fs::create_dir_all(program_dir.join("src")).unwrap();
+    std::fs::write(
+        program_dir.join("Nargo.toml"),
+        "[package]\nname = \"list\"\ntype = \"lib\"\nauthors = [\"\"]\n\n[dependencies]\n",
+    )
+    .unwrap();
+    std::fs::write(program_dir.join("src/lib.nr"), "").unwrap();
+    let _ = std::fs::remove_dir_all(&export_dir);
+
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    assert!(matches!(
+        runner.list_functions(),
+        Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound
+    ));
+
+    std::fs::create_dir_all(export_dir.join("nested.json")).unwrap();
+    std::fs::copy(
+        "tests/export/addition.json",
+        export_dir.join("addition.json"),
+    )
+    .unwrap();
+    std::fs::write(export_dir.join("notes.txt"), "").unwrap();
+
+    assert_eq!(runner.list_functions().unwrap(), ["addition"]);
+
+    let err = runner.run("additoin", BTreeMap::new()).unwrap_err();
+
+    assert!(matches!(
+        &err,
+        Error::FunctionNotFound { name, available }
+            if name == "additoin" && available == &["addition"]
+    ));
+    assert!(err.to_string().contains("[addition]"));
+}
+
+#[test]
+fn test_noir_runner_run_malformed_artifact() {
+    let program_dir = std::env::temp_dir().join("noir_runner_malformed_artifact");
+    let export_dir = program_dir.join("export");
+
+    std::fs::create_dir_all(&export_dir).unwrap();
+    std::fs::write(
+        program_dir.join("Nargo.toml"),
+        "[package]\nname = \"malformed\"\ntype = \"lib\"\nauthors = [\"\"]\n\n[dependencies]\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(program_dir.join("src")).unwrap();
+    std::fs::write(program_dir.join("src/lib.nr"), "").unwrap();
+
+    let artifact = std::fs::read_to_string("tests/export/addition.json").unwrap();
+    std::fs::write(
+        export_dir.join("truncated.json"),
+        &artifact[..artifact.len() / 2],
+    )
+    .unwrap();
+
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    assert!(matches!(
+        runner.run("truncated", BTreeMap::new()),
+        Err(Error::Serde(_))
+    ));
+}
+
+#[test]
+fn test_noir_runner_from_input_value() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Pair {
+        a: u64,
+        b: u64,
+    }
+
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 2u64.to_noir())]);
+
+    let output = runner.run("make_pair", input_map).unwrap().unwrap();
+
+    assert_eq!(
+        from_input_value::<Pair>(output).unwrap(),
+        Pair { a: 2, b: 3 }
+    );
+
+    let input_map = BTreeMap::from([
+        ("label".to_owned(), "abcd".to_noir()),
+        (
+            "pair".to_owned(),
+            noir_struct! { a: 1u64.to_noir(), b: 2u64.to_noir() },
+        ),
+    ]);
+
+    let output = runner.run("label_sum", input_map).unwrap().unwrap();
+
+    assert_eq!(
+        from_input_value::<(String, u64)>(output).unwrap(),
+        ("abcd".to_owned(), 3)
+    );
+}
+
+#[test]
+fn test_noir_runner_signed_integers() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), (-5i64).to_noir())]);
+
+    assert_eq!(
+        runner.run("negate", input_map).unwrap().unwrap(),
+        5i64.to_noir()
+    );
+
+    let input_map = BTreeMap::from([("x".to_owned(), 5i64.to_noir())]);
+
+    let output = runner.run("negate", input_map.clone()).unwrap().unwrap();
+
+    assert_eq!(output, (-5i64).to_noir());
+    assert_eq!(i64::from_noir(output).unwrap(), -5);
+    assert_eq!(
+        runner.run_typed::<i64>("negate", input_map).unwrap(),
+        Some(-5)
+    );
+}
+
+#[test]
+fn test_noir_runner_tuple_return() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2u64.to_noir()),
+        ("y".to_owned(), 3u64.to_noir()),
+    ]);
+
+    let result = runner.run("swap", input_map).unwrap().unwrap();
+
+    assert_eq!(
+        result,
+        InputValue::Vec(vec![3u64.to_noir(), 2u64.to_noir()])
+    );
+}
+
+#[test]
+fn test_noir_runner_run_with_witness() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let (result, mut witness_stack) = runner.run_with_witness("addition", input_map).unwrap();
+
+    assert_eq!(result.unwrap(), 5i128.to_noir());
+
+    let witness = witness_stack.pop().unwrap().witness;
+
+    assert!(
+        witness
+            .into_iter()
+            .any(|(_, value)| value == FieldElement::from(5u32))
+    );
+}
+
+#[test]
+fn test_noir_runner_get_abi() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let abi = runner.get_abi("addition").unwrap();
+
+    let names = abi
+        .parameters
+        .iter()
+        .map(|parameter| parameter.name.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["x", "y"]);
+    assert_eq!(abi.return_type.unwrap().abi_type, AbiType::Field);
+    assert!(matches!(
+        runner.get_abi("missing"),
+        Err(Error::FunctionNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_noir_runner_run_with_foreign_calls() {
+    use acvm::acir::brillig::{ForeignCallParam, ForeignCallResult};
+    use acvm::pwg::ForeignCallWaitInfo;
+    use nargo::foreign_calls::ForeignCallError;
+    use noir_runner::ForeignCallExecutor;
+
+    struct Doubler {
+        calls: usize,
+    }
+
+    impl ForeignCallExecutor<FieldElement> for Doubler {
+        fn execute(
+            &mut self,
+            foreign_call: &ForeignCallWaitInfo<FieldElement>,
+        ) -> Result<ForeignCallResult<FieldElement>, ForeignCallError> {
+            self.calls += 1;
+
+            let x = foreign_call.inputs[0].unwrap_field();
+
+            Ok(ForeignCallResult {
+                values: vec![ForeignCallParam::Single(x + x)],
+            })
+        }
+    }
+
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 21u64.to_noir())]);
+
+    let mut executor = Doubler { calls: 0 };
+
+    let result = runner
+        .run_with_foreign_calls("oracle_double", input_map, &mut executor)
+        .unwrap();
+
+    assert_eq!(result.unwrap(), 42u64.to_noir());
+    assert_eq!(executor.calls, 1);
+}
+
+#[test]
+fn test_noir_runner_run_capturing_prints() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let (result, lines) = runner.run_capturing_prints("print_sum", input_map).unwrap();
+
+    assert_eq!(result.unwrap(), 5i128.to_noir());
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "adding");
+}
+
+#[test]
+fn test_noir_runner_execution_error() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 1u64.to_noir())]);
+
+    let err = runner.run("assert_zero", input_map).unwrap_err();
+
+    assert!(matches!(
+        &err,
+        Error::Execution { assertion: Some(assertion), call_stack, .. }
+            if assertion == "x must be zero" && !call_stack.is_empty()
+    ));
+    assert!(err.to_string().contains("x must be zero"));
+}
+
+#[test]
+fn test_noir_runner_builder_report_diagnostics() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .report_diagnostics(false)
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 1u64.to_noir())]);
+
+    assert!(matches!(
+        runner.run("assert_zero", input_map),
+        Err(Error::Execution {
+            assertion: Some(_),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_noir_runner_from_json_bytes() {
+    let bytes = std::fs::read("tests/export/addition.json").unwrap();
+
+    let runner = NoirRunner::from_json_bytes("add", &bytes).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert_eq!(runner.list_functions().unwrap(), ["add"]);
+    assert_eq!(
+        runner.run("add", input_map.clone()).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+
+    let artifact = serde_json::from_slice(&bytes).unwrap();
+    let runner = NoirRunner::from_artifact("addition", artifact).unwrap();
+
+    assert_eq!(
+        runner.run("addition", input_map).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+
+    assert!(matches!(
+        NoirRunner::from_json_bytes("add", b"{ not json"),
+        Err(Error::Serde(_))
+    ));
+}
+
+#[test]
+fn test_noir_runner_builder_cache() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .cache(false)
+        .report_diagnostics(false)
+        .build();
+    let runner = runner.unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert_eq!(
+        runner.run("addition", input_map.clone()).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+    assert_eq!(
+        runner.run("addition", input_map).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+}
+
+#[test]
+fn test_noir_runner_builder_export_directory() {
+    let program_dir = std::env::temp_dir().join("noir_runner_export_directory");
+
+    std::fs::create_dir_all(&program_dir).unwrap();
+
+    let runner = NoirRunner::builder(program_dir)
+        .export_directory(PathBuf::from("tests/export"))
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert_eq!(runner.export_directory(), &PathBuf::from("tests/export"));
+    assert_eq!(
+        runner.run("addition", input_map).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+}
+
+#[test]
+fn test_noir_runner_128_bit_number() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), (u128::MAX - 1).to_noir())]);
+
+    assert_eq!(
+        runner.run_typed::<u128>("increment", input_map).unwrap(),
+        Some(u128::MAX)
+    );
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_noir_runner_derive() {
+    #[derive(Debug, PartialEq, noir_runner::Noir)]
+    struct DerivedPair {
+        #[noir(rename = "a")]
+        left: u64,
+        b: u64,
+    }
+
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 2u64.to_noir())]);
+
+    let result = runner
+        .run_typed::<DerivedPair>("make_pair", input_map)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result, DerivedPair { left: 2, b: 3 });
+
+    let input_map = BTreeMap::from([("pair".to_owned(), result.to_noir())]);
+
+    assert_eq!(
+        runner.run("sum_pair", input_map).unwrap().unwrap(),
+        5u64.to_noir()
+    );
+
+    #[derive(Debug, PartialEq, noir_runner::Noir)]
+    struct GenericPair<T> {
+        a: T,
+        b: T,
+    }
+
+    let input_map = BTreeMap::from([("x".to_owned(), 2u64.to_noir())]);
+
+    let result = runner
+        .run_typed::<GenericPair<u64>>("make_pair", input_map)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result, GenericPair { a: 2, b: 3 });
+
+    let input_map = BTreeMap::from([("pair".to_owned(), result.to_noir())]);
+
+    assert_eq!(
+        runner.run("sum_pair", input_map).unwrap().unwrap(),
+        5u64.to_noir()
+    );
+}
+
+#[test]
+fn test_noir_runner_prove_missing_prover() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .prover_binary(PathBuf::from("missing-bb-binary"))
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert!(matches!(
+        runner.prove("addition", input_map),
+        Err(Error::Prover(_))
+    ));
+    assert!(matches!(
+        runner.verify("addition", &[], &[]),
+        Err(Error::Prover(_))
+    ));
+}
+
+#[test]
+fn test_noir_runner_public_inputs() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    assert_eq!(
+        runner.public_parameters("add_public").unwrap(),
+        vec!["x".to_owned()]
+    );
+    assert!(runner.public_parameters("addition").unwrap().is_empty());
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let (result, public_inputs) = runner
+        .run_with_public_inputs("add_public", input_map)
+        .unwrap();
+
+    assert_eq!(result.unwrap(), 5i128.to_noir());
+    assert_eq!(public_inputs.first(), Some(&FieldElement::from(2u128)));
+}
+
+#[test]
+fn test_noir_runner_run_typed_unit() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 2i128.to_noir()),
+    ]);
+
+    assert_eq!(
+        runner.run_typed::<()>("assert_equal", input_map).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_noir_runner_tuple() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("pair".to_owned(), (2u64, 3u64).to_noir())]);
+
+    assert_eq!(
+        runner.run("sum_tuple", input_map).unwrap().unwrap(),
+        5u64.to_noir()
+    );
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 1u64.to_noir()),
+        ("y".to_owned(), 2u64.to_noir()),
+    ]);
+
+    let result = runner
+        .run_typed::<(u64, u64)>("swap", input_map)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result, (2, 1));
+}
+
+#[test]
+fn test_noir_runner_str_length() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("greeting".to_owned(), "hello".to_noir())]);
+
+    let result = runner.run("first_byte", input_map).unwrap().unwrap();
+
+    assert_eq!(result, 'h'.to_noir());
+
+    let input_map = BTreeMap::from([("greeting".to_owned(), "hello!".to_noir())]);
+
+    let result = runner.run("first_byte", input_map);
+
+    assert!(matches!(
+        result,
+        Err(Error::LengthMismatch { param, expected: 5, found: 6 }) if param == "greeting"
+    ));
+}
+
+#[test]
+fn test_noir_runner_run_typed_u64() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2u64.to_noir()),
+        ("y".to_owned(), 3u64.to_noir()),
+    ]);
+
+    let sum: u64 = runner
+        .run_typed("addition", input_map.clone())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(sum, 5);
+
+    let result = runner.run_typed::<String>("addition", input_map);
+
+    assert!(matches!(result, Err(Error::ReturnTypeMismatch { .. })));
+}
+
+#[test]
+fn test_noir_runner_run_with_input() {
+    #[derive(Serialize)]
+    struct Addition {
+        x: u64,
+        y: u64,
+    }
+
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let result = runner
+        .run_with_input("addition", &Addition { x: 2, y: 3 })
+        .unwrap();
+
+    assert_eq!(result.unwrap(), 5u64.to_noir());
+
+    let result = runner.run_with_input("addition", &serde_json::json!({ "x": 1, "y": 2 }));
+
+    assert_eq!(result.unwrap().unwrap(), 3u64.to_noir());
+
+    let result = runner.run_with_input("addition", &5u64);
+
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+}
+
+#[test]
+fn test_noir_runner_version_mismatch() {
+    let artifact = std::fs::read_to_string("tests/export/addition.json").unwrap();
+    let artifact = artifact.replace(
+        r#""noir_version":"1.0.0-beta.3+"#,
+        r#""noir_version":"0.36.0+"#,
+    );
+
+    let runner = NoirRunner::from_json_bytes("addition", artifact.as_bytes()).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    assert!(matches!(
+        runner.run("addition", input_map),
+        Err(Error::VersionMismatch { expected, found })
+            if expected == "1.0.0-beta.3" && found == "0.36.0"
+    ));
+}
+
+#[test]
+fn test_noir_runner_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<NoirRunner>();
+    assert_send_sync::<noir_runner::NoirRunnerBuilder>();
+    assert_send_sync::<noir_runner::RunSession<'static>>();
+
+    let program_dir = PathBuf::from("tests");
+    let runner = std::sync::Arc::new(NoirRunner::try_new(program_dir).unwrap());
+
+    let handles = (0..4i128)
+        .map(|x| {
+            let runner = runner.clone();
+
+            std::thread::spawn(move || {
+                let input_map = BTreeMap::from([
+                    ("x".to_owned(), x.to_noir()),
+                    ("y".to_owned(), 1i128.to_noir()),
+                ]);
+
+                runner.run("addition", input_map).unwrap().unwrap()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for (x, handle) in (0..4i128).zip(handles) {
+        assert_eq!(handle.join().unwrap(), (x + 1).to_noir());
+    }
+}
+
+#[test]
+fn test_noir_runner_run_with_solver() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), [1u8, 2, 3, 4].to_noir())]);
+
+    let solver = noir_runner::Bn254BlackBoxSolver(false);
+
+    let result = runner
+        .run_with_solver("blake3_hash", input_map.clone(), &solver)
+        .unwrap();
+
+    assert_eq!(result, runner.run("blake3_hash", input_map).unwrap());
+}
+
+#[test]
+fn test_noir_runner_run_with_solver_memoized() {
+    use acvm::BlackBoxResolutionError;
+    use noir_runner::{BlackBoxFunctionSolver, Bn254BlackBoxSolver};
+    use std::cell::Cell;
+
+    struct RecordingSolver {
+        permutations: Cell<usize>,
+    }
+
+    impl BlackBoxFunctionSolver<FieldElement> for RecordingSolver {
+        fn pedantic_solving(&self) -> bool {
+            false
+        }
+
+        fn multi_scalar_mul(
+            &self,
+            points: &[FieldElement],
+            scalars_lo: &[FieldElement],
+            scalars_hi: &[FieldElement],
+        ) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+            Bn254BlackBoxSolver(false).multi_scalar_mul(points, scalars_lo, scalars_hi)
+        }
+
+        fn ec_add(
+            &self,
+            input1_x: &FieldElement,
+            input1_y: &FieldElement,
+            input1_infinite: &FieldElement,
+            input2_x: &FieldElement,
+            input2_y: &FieldElement,
+            input2_infinite: &FieldElement,
+        ) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+            Bn254BlackBoxSolver(false).ec_add(
+                input1_x,
+                input1_y,
+                input1_infinite,
+                input2_x,
+                input2_y,
+                input2_infinite,
+            )
+        }
+
+        fn poseidon2_permutation(
+            &self,
+            inputs: &[FieldElement],
+            len: u32,
+        ) -> Result<Vec<FieldElement>, BlackBoxResolutionError> {
+            self.permutations.set(self.permutations.get() + 1);
+
+            Bn254BlackBoxSolver(false).poseidon2_permutation(inputs, len)
+        }
+    }
+
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .memoize_results(8)
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), [1u64, 2, 3, 4].to_noir())]);
+
+    let expected = runner.run("poseidon2_permute", input_map.clone()).unwrap();
+
+    let solver = RecordingSolver {
+        permutations: Cell::new(0),
+    };
+
+    for calls in 1..=2 {
+        let result = runner.run_with_solver("poseidon2_permute", input_map.clone(), &solver);
+
+        assert_eq!(result.unwrap(), expected);
+        assert_eq!(solver.permutations.get(), calls);
+    }
+
+    assert_eq!(runner.result_cache_hits(), 0);
+}
+
+#[test]
+fn test_noir_runner_reload() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    runner.run("addition", input_map.clone()).unwrap();
+    runner.reload().unwrap();
+
+    assert_eq!(
+        runner.run("addition", input_map).unwrap().unwrap(),
+        5i128.to_noir()
+    );
+
+    let artifact = std::fs::read("tests/export/addition.json").unwrap();
+    let runner = NoirRunner::from_json_bytes("addition", &artifact).unwrap();
+
+    runner.reload().unwrap();
+}
+
+#[test]
+fn test_noir_runner_run_artifact() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let artifact_path = std::env::temp_dir().join("noir_runner_run_artifact.json");
+    std::fs::copy("tests/export/addition.json", &artifact_path).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2i128.to_noir()),
+        ("y".to_owned(), 3i128.to_noir()),
+    ]);
+
+    let result = runner
+        .run_artifact(&artifact_path, input_map.clone())
+        .unwrap();
+
+    assert_eq!(result.unwrap(), 5i128.to_noir());
+
+    let missing = std::env::temp_dir().join("noir_runner_missing_artifact.json");
+
+    assert!(matches!(
+        runner.run_artifact(&missing, input_map),
+        Err(Error::Io(_))
+    ));
+}
+
+#[test]
+fn test_noir_runner_assertion_message() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::builder(program_dir)
+        .report_diagnostics(false)
+        .build()
+        .unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2u64.to_noir()),
+        ("y".to_owned(), 2u64.to_noir()),
+    ]);
+
+    let err = runner
+        .run("assert_not_equal", input_map.clone())
+        .unwrap_err();
+
+    assert_eq!(err.assertion_message(), Some("x must not equal y"));
+
+    let err = runner
+        .run_batch("assert_not_equal", vec![input_map])
+        .unwrap_err();
+
+    assert_eq!(err.assertion_message(), Some("x must not equal y"));
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2u64.to_noir()),
+        ("y".to_owned(), 3u64.to_noir()),
+    ]);
+
+    assert_eq!(runner.run("assert_not_equal", input_map).unwrap(), None);
+}
+
+#[test]
+fn test_noir_runner_run_with_initial_witness() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 2u64.to_noir()),
+        ("y".to_owned(), 3u64.to_noir()),
+    ]);
+
+    let initial_witness = runner
+        .get_abi("addition")
+        .unwrap()
+        .encode(&input_map, None)
+        .unwrap();
+
+    let result = runner
+        .run_with_initial_witness("addition", initial_witness)
+        .unwrap();
+
+    assert_eq!(result, Some(5u64.to_noir()));
+}
+
+#[test]
+fn test_noir_runner_no_inputs() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let result = runner.run("constant", BTreeMap::new()).unwrap();
+
+    assert_eq!(result, Some(42u64.to_noir()));
+
+    let result = runner
+        .run_typed::<u64>("constant", BTreeMap::new())
+        .unwrap();
+
+    assert_eq!(result, Some(42));
+
+    assert_eq!(runner.signature("constant").unwrap(), "constant() -> Field");
+}
+
+#[test]
+fn test_noir_runner_brillig() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("values".to_owned(), [1u32, 2, 3, 4].to_noir())]);
+
+    assert_eq!(
+        runner.run("brillig_sum", input_map.clone()).unwrap(),
+        Some(10u32.to_noir())
+    );
+
+    let (result, lines) = runner
+        .run_capturing_prints("brillig_sum", input_map)
+        .unwrap();
+
+    assert_eq!(result, Some(10u32.to_noir()));
+    assert_eq!(lines, ["summing"]);
+
+    let input_map = BTreeMap::from([("values".to_owned(), [1u32, 0, 3, 4].to_noir())]);
+
+    let err = runner.run("brillig_sum", input_map).unwrap_err();
+
+    assert_eq!(err.assertion_message(), Some("values must be non-zero"));
+}
+
+#[test]
+fn test_noir_runner_brillig_oracle() {
+    use acvm::acir::brillig::{ForeignCallParam, ForeignCallResult};
+    use acvm::pwg::ForeignCallWaitInfo;
+    use nargo::foreign_calls::ForeignCallError;
+    use noir_runner::ForeignCallExecutor;
+
+    struct Doubler;
+
+    impl ForeignCallExecutor<FieldElement> for Doubler {
+        fn execute(
+            &mut self,
+            foreign_call: &ForeignCallWaitInfo<FieldElement>,
+        ) -> Result<ForeignCallResult<FieldElement>, ForeignCallError> {
+            let x = foreign_call.inputs[0].unwrap_field();
+
+            Ok(ForeignCallResult {
+                values: vec![ForeignCallParam::Single(x + x)],
+            })
+        }
+    }
+
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let input_map = BTreeMap::from([("x".to_owned(), 21u64.to_noir())]);
+
+    let result = runner
+        .run_with_foreign_calls("brillig_oracle_double", input_map, &mut Doubler)
+        .unwrap();
+
+    assert_eq!(result, Some(42u64.to_noir()));
+}
+
+#[cfg(feature = "compile")]
+#[test]
+fn test_noir_runner_compile_and_run() {
+    let program_dir = PathBuf::from("tests");
+    let runner = NoirRunner::try_new(program_dir).unwrap();
+
+    let source = "
+        #[export]
+        fn multiply(x: Field, y: Field) -> Field {
+            x * y
+        }
+
+        fn main(x: u32) -> pub u32 {
+            x + 1
+        }
+    ";
+
+    let input_map = BTreeMap::from([
+        ("x".to_owned(), 6u64.to_noir()),
+        ("y".to_owned(), 7u64.to_noir()),
+    ]);
+
+    let result = runner
+        .compile_and_run(source, "multiply", input_map)
+        .unwrap();
+
+    assert_eq!(result, Some(42u64.to_noir()));
+
+    let input_map = BTreeMap::from([("x".to_owned(), 1u32.to_noir())]);
+
+    let result = runner.compile_and_run(source, "main", input_map).unwrap();
+
+    assert_eq!(result, Some(2u32.to_noir()));
+
+    assert!(matches!(
+        runner.compile_and_run(source, "missing", BTreeMap::new()),
+        Err(Error::FunctionNotFound { available, .. }) if available.contains(&"multiply".to_owned())
+    ));
+
+    assert!(matches!(
+        runner.compile_and_run("fn main( {", "main", BTreeMap::new()),
+        Err(Error::Compile(_))
+    ));
+}